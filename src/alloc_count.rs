@@ -0,0 +1,166 @@
+use crate::encoder::Encoder;
+use crate::hpack::{Hpack, STATIC_TABLE};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Global allocator that forwards to the system allocator while counting the allocations made on each thread.
+/// Install it in a test binary to make `measure` report real numbers:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: simple_hpack::alloc_count::CountingAllocator = simple_hpack::alloc_count::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+/// Allocation counts recorded on the current thread while a closure ran
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub deallocations: usize,
+    pub bytes_allocated: usize,
+}
+
+thread_local! {
+    static STATS: Cell<AllocStats> = const { Cell::new(AllocStats{allocations: 0, reallocations: 0, deallocations: 0, bytes_allocated: 0}) };
+}
+
+fn record<F: FnOnce(&mut AllocStats)>(f: F) {
+    let _ = STATS.try_with(|stats| {
+        let mut current = stats.get();
+        f(&mut current);
+        stats.set(current);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|s| { s.allocations += 1; s.bytes_allocated += layout.size(); });
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(|s| { s.allocations += 1; s.bytes_allocated += layout.size(); });
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|s| { s.reallocations += 1; s.bytes_allocated += new_size.saturating_sub(layout.size()); });
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|s| s.deallocations += 1);
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Function that runs a closure and returns its result along with the allocations it made on this thread,
+/// the counts are only non zero when `CountingAllocator` is the global allocator
+///
+/// ## Arguments
+///
+/// * f - the closure to measure
+///
+/// ## Returns
+///
+/// * (R, AllocStats) - the result of the closure and the allocations made while it ran
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> (R, AllocStats) {
+    let before = STATS.with(Cell::get);
+    let result = f();
+    let after = STATS.with(Cell::get);
+
+    (result, AllocStats{
+        allocations: after.allocations - before.allocations,
+        reallocations: after.reallocations - before.reallocations,
+        deallocations: after.deallocations - before.deallocations,
+        bytes_allocated: after.bytes_allocated - before.bytes_allocated,
+    })
+}
+
+/// Reference request header lists, mixing static table hits with literals that get added to the dynamic table
+pub static REFERENCE_REQUESTS: [&[(&str, &str)]; 2] = [
+    &[(":method", "GET"), (":scheme", "https"), (":path", "/index.html"), (":authority", "www.example.com"), ("custom-key", "custom-value")],
+    &[(":method", "POST"), (":scheme", "http"), (":path", "/"), ("user-agent", "simple_hpack"), ("cache-control", "no-cache")],
+];
+
+/// Function that encodes the reference requests with a fresh encoder, lazily built tables are initialized before measuring starts
+///
+/// ## Returns
+///
+/// * (Vec<Vec<u8>>, AllocStats) - the encoded header blocks and the allocations made while encoding them
+pub fn measure_reference_encode() -> (Vec<Vec<u8>>, AllocStats) {
+    lazy_static::initialize(&STATIC_TABLE);
+    let mut encoder = Encoder::new(4096);
+    let mut blocks = Vec::with_capacity(REFERENCE_REQUESTS.len());
+
+    let ((), stats) = measure(|| {
+        for headers in REFERENCE_REQUESTS.iter() {
+            blocks.push(encoder.encode(headers).expect("reference headers encode"));
+        }
+    });
+    (blocks, stats)
+}
+
+/// Function that decodes the reference requests with a fresh decoder, the blocks are encoded before measuring starts
+///
+/// ## Returns
+///
+/// * AllocStats - the allocations made while decoding the header blocks
+pub fn measure_reference_decode() -> AllocStats {
+    let (blocks, _) = measure_reference_encode();
+    let mut decoder = Hpack::new(4096);
+
+    let ((), stats) = measure(|| {
+        for block in blocks {
+            decoder.read_headers(block).expect("reference blocks decode");
+        }
+    });
+    stats
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_measure_counts_allocations(){
+        let (_, stats) = measure(|| Vec::<u8>::with_capacity(16));
+
+        assert_eq!(1, stats.allocations);
+        assert_eq!(16, stats.bytes_allocated);
+    }
+
+    #[test]
+    fn test_measure_no_allocations(){
+        let (_, stats) = measure(|| 1 + 1);
+
+        assert_eq!(AllocStats::default(), stats);
+    }
+
+    #[test]
+    fn test_reference_encode_allocations(){
+        let (_, stats) = measure_reference_encode();
+
+        assert!(stats.allocations <= MAX_ENCODE_ALLOCATIONS, "encode made {} allocations", stats.allocations);
+        assert!(stats.reallocations <= MAX_ENCODE_REALLOCATIONS, "encode made {} reallocations", stats.reallocations);
+    }
+
+    #[test]
+    fn test_reference_decode_allocations(){
+        let stats = measure_reference_decode();
+
+        assert!(stats.allocations <= MAX_DECODE_ALLOCATIONS, "decode made {} allocations", stats.allocations);
+        assert!(stats.reallocations <= MAX_DECODE_REALLOCATIONS, "decode made {} reallocations", stats.reallocations);
+    }
+
+    // Current counts for the reference workloads, lower these as allocations are removed
+    const MAX_ENCODE_ALLOCATIONS: usize = 21;
+    const MAX_ENCODE_REALLOCATIONS: usize = 10;
+    const MAX_DECODE_ALLOCATIONS: usize = 34;
+    const MAX_DECODE_REALLOCATIONS: usize = 2;
+}
@@ -0,0 +1,247 @@
+use crate::encoder::Encoder;
+use crate::hpack::{as_pairs, Header, Hpack, ERROR_HEADER_LIST_SIZE};
+use crate::pseudo;
+
+/// Initial SETTINGS_HEADER_TABLE_SIZE as per [IETF RFC 7540 Section 6.5.2](https://tools.ietf.org/html/rfc7540#section-6.5.2)
+pub static DEFAULT_HEADER_TABLE_SIZE: usize = 4096;
+
+/// One end of an HTTP/2 connection, owning the encoder for outgoing header blocks and the decoder for
+/// incoming ones so their settings cannot drift apart.
+pub struct Connection{
+    encoder: Encoder,
+    decoder: Hpack,
+    local_table_size: usize,
+    peer_table_size: usize,
+    max_header_list_size: Option<usize>,
+}
+
+impl Connection{
+    /// Builds a new connection end from the two SETTINGS_HEADER_TABLE_SIZE values as per [IETF RFC 7540 Section 6.5.2](https://tools.ietf.org/html/rfc7540#section-6.5.2)
+    ///
+    /// ## Arguments
+    ///
+    /// * local_table_size - the table size advertised by this end, bounding the decoder's dynamic table
+    /// * peer_table_size - the table size advertised by the peer, bounding the encoder's dynamic table
+    ///
+    /// ## Returns
+    ///
+    /// A new connection with empty dynamic tables and no header list size limit. If the peer's table size is not
+    /// the initial 4096 bytes, the first encoded header block starts with a Dynamic Table Size Update.
+    pub fn new(local_table_size: usize, peer_table_size: usize) -> Connection{
+        let mut encoder = Encoder::new(peer_table_size);
+        if peer_table_size != DEFAULT_HEADER_TABLE_SIZE {
+            encoder.set_table_size(peer_table_size);
+        }
+
+        Connection{
+            encoder,
+            decoder: Hpack::new(local_table_size),
+            local_table_size,
+            peer_table_size,
+            max_header_list_size: None,
+        }
+    }
+
+    /// Function that returns the table size advertised by this end
+    pub fn local_table_size(&self) -> usize {
+        self.local_table_size
+    }
+
+    /// Function that returns the table size advertised by the peer
+    pub fn peer_table_size(&self) -> usize {
+        self.peer_table_size
+    }
+
+    /// Function that returns the header list size limit applied to both directions, if any
+    pub fn max_header_list_size(&self) -> Option<usize> {
+        self.max_header_list_size
+    }
+
+    /// Function used when this end advertises a new table size, the decoder's table is resized once the peer's size update arrives
+    pub fn set_local_table_size(&mut self, size: usize) {
+        self.local_table_size = size;
+        self.decoder.set_max_table_size(size);
+    }
+
+    /// Function used when the peer advertises a new table size, the encoder signals the change in its next header block
+    pub fn set_peer_table_size(&mut self, size: usize) {
+        self.peer_table_size = size;
+        self.encoder.set_table_size(size);
+    }
+
+    /// Function used to limit the size of header lists sent and received, as measured by [IETF RFC 7540 Section 6.5.2](https://tools.ietf.org/html/rfc7540#section-6.5.2)
+    pub fn set_max_header_list_size(&mut self, size: Option<usize>) {
+        self.max_header_list_size = size;
+        self.decoder.set_max_header_list_size(size);
+    }
+
+    /// Function used to set the largest header entry the encoder will add to its dynamic table, see `Encoder::set_index_size_threshold`
+    pub fn set_index_size_threshold(&mut self, threshold: Option<usize>) {
+        self.encoder.set_index_size_threshold(threshold);
+    }
+
+    /// Function used to encode an outgoing list of headers
+    ///
+    /// ## Arguments
+    ///
+    /// * headers - the name and value pairs to encode, in order
+    ///
+    /// ## Returns
+    ///
+    /// * Result<Vec<u8>,&'static str> - the encoded header block or an error message
+    pub fn encode(&mut self, headers: &[(&str, &str)]) -> Result<Vec<u8>, &'static str>{
+        let size = headers.iter().map(|(name, value)| name.len() + value.len() + 32).sum();
+        self.check_list_size(size)?;
+        self.encoder.encode(headers)
+    }
+
+    /// Function used to decode an incoming header block
+    ///
+    /// ## Arguments
+    ///
+    /// * stream - the bytes of the header block
+    ///
+    /// ## Returns
+    ///
+    /// * Result<Vec<Header>,&'static str> - the decoded headers or an error message
+    pub fn decode(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str>{
+        self.decoder.read_headers(stream)
+    }
+
+    /// Function used to encode an outgoing request, rejecting it if its pseudo-header fields are invalid, see `pseudo::validate_request`
+    pub fn encode_request(&mut self, headers: &[(&str, &str)]) -> Result<Vec<u8>, &'static str>{
+        pseudo::validate_request(headers)?;
+        self.encode(headers)
+    }
+
+    /// Function used to encode an outgoing response, rejecting it if its pseudo-header fields are invalid, see `pseudo::validate_response`
+    pub fn encode_response(&mut self, headers: &[(&str, &str)]) -> Result<Vec<u8>, &'static str>{
+        pseudo::validate_response(headers)?;
+        self.encode(headers)
+    }
+
+    /// Function used to decode an incoming request, rejecting it if its pseudo-header fields are invalid, see `pseudo::validate_request`
+    pub fn decode_request(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str>{
+        let headers = self.decode(stream)?;
+        pseudo::validate_request(&as_pairs(&headers))?;
+        Ok(headers)
+    }
+
+    /// Function used to decode an incoming response, rejecting it if its pseudo-header fields are invalid, see `pseudo::validate_response`
+    pub fn decode_response(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str>{
+        let headers = self.decode(stream)?;
+        pseudo::validate_response(&as_pairs(&headers))?;
+        Ok(headers)
+    }
+
+    fn check_list_size(&self, size: usize) -> Result<(), &'static str> {
+        match self.max_header_list_size {
+            Some(max) if size > max => Err(ERROR_HEADER_LIST_SIZE),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_connection_round_trip(){
+        let mut client = Connection::new(4096, 4096);
+        let mut server = Connection::new(4096, 4096);
+
+        let block = client.encode(&[(":method", "GET"), ("accept-charset", "set"), ("key", "abc")]).unwrap();
+        let headers = server.decode(block).unwrap();
+
+        let names: Vec<(&str, &str)> = headers.iter().map(|h| (h.name(), h.value())).collect();
+        assert_eq!(vec![(":method", "GET"), ("accept-charset", "set"), ("key", "abc")], names);
+    }
+
+    #[test]
+    fn test_connection_peer_table_size_update(){
+        let mut connection = Connection::new(4096, 4096);
+        connection.set_peer_table_size(64);
+
+        assert_eq!(64, connection.peer_table_size());
+        assert_eq!(vec![63_u8, 33_u8, 130_u8], connection.encode(&[(":method", "GET")]).unwrap());
+    }
+
+    #[test]
+    fn test_connection_max_header_list_size(){
+        let mut connection = Connection::new(4096, 4096);
+        connection.set_max_header_list_size(Some(50));
+
+        assert!(connection.encode(&[(":method", "GET")]).is_ok());
+        assert!(connection.encode(&[(":method", "GET"), (":path", "/")]).is_err());
+        assert!(connection.decode(vec![130_u8, 132_u8]).is_err());
+    }
+
+    #[test]
+    fn test_connection_extended_connect_request(){
+        let mut client = Connection::new(4096, 4096);
+        let mut server = Connection::new(4096, 4096);
+        let request = [(":method", "CONNECT"), (":protocol", "websocket"), (":scheme", "https"), (":path", "/chat"), (":authority", "example.com")];
+
+        let block = client.encode_request(&request).unwrap();
+        let headers = server.decode_request(block).unwrap();
+
+        assert_eq!("websocket", headers[1].value());
+        assert!(client.encode_request(&[(":method", "GET"), (":protocol", "websocket"), (":scheme", "https"), (":path", "/")]).is_err());
+    }
+
+    #[test]
+    fn test_connection_decode_response_rejects_request(){
+        let mut connection = Connection::new(4096, 4096);
+
+        assert!(connection.decode_response(vec![130_u8, 135_u8, 132_u8]).is_err());
+        assert!(connection.decode_response(vec![136_u8]).is_ok());
+    }
+
+    #[test]
+    fn test_connection_decode_malformed(){
+        let mut connection = Connection::new(4096, 4096);
+
+        assert!(connection.decode(vec![0xff]).is_err());
+        assert!(connection.decode(vec![0x40]).is_err());
+        assert!(connection.decode(vec![0xff, 0xff, 0xff, 0xff, 0xff, 0x0f]).is_err());
+    }
+
+    #[test]
+    fn test_connection_local_table_size_waits_for_update(){
+        let mut client = Connection::new(4096, 4096);
+        let mut server = Connection::new(4096, 4096);
+
+        server.decode(client.encode(&[("key", "abc")]).unwrap()).unwrap();
+        server.set_local_table_size(0);
+        let headers = server.decode(client.encode(&[("key", "abc")]).unwrap()).unwrap();
+        assert_eq!("abc", headers[0].value());
+
+        client.set_peer_table_size(0);
+        server.decode(client.encode(&[("key", "abc")]).unwrap()).unwrap();
+        assert!(server.decoder.table().is_empty());
+        assert!(server.decode(vec![0x3f, 0x61]).is_err());
+    }
+
+    #[test]
+    fn test_connection_oversized_header_not_sensitive(){
+        let mut client = Connection::new(4096, 4096);
+        let mut server = Connection::new(4096, 4096);
+        client.set_index_size_threshold(Some(100));
+        let cookie = "a".repeat(200);
+
+        let headers = server.decode(client.encode(&[("cookie", &cookie)]).unwrap()).unwrap();
+
+        assert!(!headers[0].is_sensitive());
+        assert_eq!(format!("cookie: {}", cookie), headers[0].to_string());
+    }
+
+    #[test]
+    fn test_connection_new_announces_peer_table_size(){
+        let mut connection = Connection::new(4096, 64);
+
+        assert_eq!(vec![63_u8, 33_u8, 130_u8], connection.encode(&[(":method", "GET")]).unwrap());
+        assert_eq!(vec![130_u8], connection.encode(&[(":method", "GET")]).unwrap());
+        assert_eq!(vec![130_u8], Connection::new(4096, 4096).encode(&[(":method", "GET")]).unwrap());
+    }
+}
@@ -4,6 +4,63 @@ pub struct DynamicTable{
     current_size: usize,
 }
 
+/// The FIFO behaviour `Hpack` and `Encoder` need from a dynamic table as per [IETF RFC 7541 Section 4](https://tools.ietf.org/html/rfc7541#section-4),
+/// implement it to plug in shared, instrumented or fixed capacity tables. Positions start at 0 for the newest entry.
+pub trait Table {
+    /// Function that returns the name and value of the entry at the given position, if any
+    fn get(&self, index: usize) -> Option<(&str, &str)>;
+
+    /// Function used to insert a new entry, evicting the oldest entries until it fits. An entry larger then the table
+    /// must leave the table empty as per [IETF RFC 7541 Section 4.4](https://tools.ietf.org/html/rfc7541#section-4.4), and errors.
+    fn add(&mut self, header: (String, String)) -> Result<(), &'static str>;
+
+    /// Function used to set the table size, evicting the oldest entries until the table fits
+    fn set_size(&mut self, new_size: usize);
+
+    /// Function that returns the number of entries in the table
+    fn len(&self) -> usize;
+
+    /// Function that returns true if the table holds no entries
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Function that returns the size in bytes of the entries in the table, as per [IETF RFC 7541 Section 4.1](https://tools.ietf.org/html/rfc7541#section-4.1)
+    fn size(&self) -> usize;
+
+    /// Function that returns the position of the newest entry matching both name and value
+    fn contains(&self, name: &str, value: &str) -> Option<usize> {
+        (0..).map_while(|i| self.get(i)).position(|x| x.0 == name && x.1 == value)
+    }
+
+    /// Function that returns the position of the newest entry matching the name
+    fn find_name(&self, name: &str) -> Option<usize> {
+        (0..).map_while(|i| self.get(i)).position(|x| x.0 == name)
+    }
+}
+
+impl Table for DynamicTable {
+    fn get(&self, index: usize) -> Option<(&str, &str)> {
+        DynamicTable::get(self, index).map(|x| (x.0.as_str(), x.1.as_str()))
+    }
+
+    fn add(&mut self, header: (String, String)) -> Result<(), &'static str> {
+        DynamicTable::add(self, header)
+    }
+
+    fn set_size(&mut self, new_size: usize) {
+        DynamicTable::set_size(self, new_size)
+    }
+
+    fn len(&self) -> usize {
+        DynamicTable::len(self)
+    }
+
+    fn size(&self) -> usize {
+        DynamicTable::size(self)
+    }
+}
+
 impl DynamicTable {
     /// Builds a new dynamic table of a given size in bytes, fucntions as a FIFO list of headers as per [IETF RFC 7541 Section 4](https://tools.ietf.org/html/rfc7541#section-4)
     /// 
@@ -23,6 +80,48 @@ impl DynamicTable {
         self.table.get(index)
     }
 
+    /// Function that returns the number of entries in the table
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Function that returns true if the table holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Function that returns the current size of the table in bytes, as per [IETF RFC 7541 Section 4.1](https://tools.ietf.org/html/rfc7541#section-4.1)
+    pub fn size(&self) -> usize {
+        self.current_size
+    }
+
+    /// Function that searches the table for an entry matching both name and value
+    /// 
+    /// ## Arguments
+    /// 
+    /// * name - the header name to look for
+    /// * value - the header value to look for
+    /// 
+    /// ## Returns
+    /// 
+    /// The position of the newest matching entry, usable with `get`, or None if there is no match
+    pub fn contains(&self, name: &str, value: &str) -> Option<usize> {
+        self.table.iter().position(|x| x.0 == name && x.1 == value)
+    }
+
+    /// Function that searches the table for an entry matching the name, regardless of its value
+    /// 
+    /// ## Arguments
+    /// 
+    /// * name - the header name to look for
+    /// 
+    /// ## Returns
+    /// 
+    /// The position of the newest entry with that name, usable with `get`, or None if there is no match
+    pub fn find_name(&self, name: &str) -> Option<usize> {
+        self.table.iter().position(|x| x.0 == name)
+    }
+
     /// Function used to add an entry to the dynamic table in FIFO format as per [IETF RFC 7541 Section 2.3](https://tools.ietf.org/html/rfc7541#section-2.3.2)
     /// 
     /// ## Arguments
@@ -31,21 +130,20 @@ impl DynamicTable {
     /// 
     /// ## Returns
     /// 
-    /// An error if the header is larger then the table size
+    /// An error if the header is larger then the table size, in which case the table is emptied as per [IETF RFC 7541 Section 4.4](https://tools.ietf.org/html/rfc7541#section-4.4)
     pub fn add(&mut self, header: (String,String)) -> Result<(),&'static str>{
-        let header_size = header.0.capacity() + header.1.capacity() + 32;
+        let header_size = header.0.len() + header.1.len() + 32;
         if header_size > self.table_size {
+            self.reduce_size(0);
             Err("Header exceeds table size!")
         } else {
-            println!("Adding header - {:?}, size - {}",header, header_size);
             let reamining_space = self.table_size - self.current_size;
 
             if reamining_space < header_size{
-                println!("Removing header! header_size - {}, remaining_size - {}", header_size, reamining_space);
                 self.reduce_size(self.table_size - header_size);
             }
 
-            self.current_size = self.current_size + header_size;
+            self.current_size += header_size;
             self.table.insert(0, header);
             Ok(())
         }
@@ -73,12 +171,10 @@ impl DynamicTable {
     /// 
     /// Nothing
     fn reduce_size(&mut self, new_size: usize){
-        println!("cur size - {}, new size - {}", self.current_size, new_size);
         while self.current_size > new_size {
             let header = self.table.pop();
-            println!("Removing - {:?}, cur size - {}", header, self.current_size);
             match header {
-                Some(x) => self.current_size = self.current_size - (x.0.capacity() + x.1.capacity() + 32),
+                Some(x) => self.current_size -= x.0.len() + x.1.len() + 32,
                 None => panic!("Oh boy batman, i shouldent be here!")
             } 
         }
@@ -105,6 +201,17 @@ mod test {
         assert!(table.add((String::from("This is too large!"), String::from("Still too long"))).is_err())
     }
 
+    #[test]
+    fn test_dynamic_table_add_too_large_empties_table(){
+        let mut table = DynamicTable::new(50);
+
+        table.add((String::from("This"), String::from("Fits"))).unwrap();
+
+        assert!(table.add((String::from("This is too large!"), String::from("Still too long"))).is_err());
+        assert!(table.is_empty());
+        assert_eq!(0, table.size());
+    }
+
     #[test]
     fn test_dynamic_table_add_removes_oldest(){
         let mut table = DynamicTable::new(83);
@@ -166,4 +273,27 @@ mod test {
 
         assert!(table.table.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dynamic_table_contains(){
+        let mut table = DynamicTable::new(128);
+
+        table.add((String::from("Test"), String::from("Head"))).unwrap();
+        table.add((String::from("Test"), String::from("Head2"))).unwrap();
+
+        assert_eq!(Some(1), table.contains("Test", "Head"));
+        assert_eq!(Some(0), table.contains("Test", "Head2"));
+        assert_eq!(None, table.contains("Test", "Head3"));
+    }
+
+    #[test]
+    fn test_dynamic_table_find_name(){
+        let mut table = DynamicTable::new(128);
+
+        table.add((String::from("Test"), String::from("Head"))).unwrap();
+        table.add((String::from("Other"), String::from("Head2"))).unwrap();
+
+        assert_eq!(Some(1), table.find_name("Test"));
+        assert_eq!(None, table.find_name("Missing"));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::dyn_table::{DynamicTable, Table};
+use crate::hpack::STATIC_TABLE;
+use crate::{new_indexed, new_literal, new_size_update, not_indexed};
+
+pub struct Encoder<T: Table = DynamicTable>{
+    dynamic_table: T,
+    /// Smallest and final table sizes set since the last header block
+    pending_size_update: Option<(usize, usize)>,
+    index_size_threshold: Option<usize>,
+}
+
+impl Encoder{
+    pub fn new(dynamic_table_size: usize) -> Encoder{
+        Encoder::with_table(DynamicTable::new(dynamic_table_size))
+    }
+}
+
+impl<T: Table> Encoder<T>{
+    /// Builds a new encoder on top of the given dynamic table implementation
+    pub fn with_table(dynamic_table: T) -> Encoder<T>{
+        Encoder{dynamic_table, pending_size_update: None, index_size_threshold: None}
+    }
+
+    /// Function that returns the dynamic table used for encoding
+    pub fn table(&self) -> &T {
+        &self.dynamic_table
+    }
+
+    /// Function used to change the size of the dynamic table used for encoding, a Dynamic Table Size Update
+    /// is emitted at the start of the next header block as per [IETF RFC 7541 Section 4.2](https://tools.ietf.org/html/rfc7541#section-4.2).
+    /// If the size shrank below the final value in between, the smallest size is signalled first.
+    ///
+    /// ## Arguments
+    ///
+    /// * size - the new size in bytes of the dynamic table
+    pub fn set_table_size(&mut self, size: usize) {
+        self.dynamic_table.set_size(size);
+        self.pending_size_update = match self.pending_size_update {
+            Some((smallest, _)) => Some((smallest.min(size), size)),
+            None => Some((size, size)),
+        };
+    }
+
+    /// Function used to set the largest entry size, as per [IETF RFC 7541 Section 4.1](https://tools.ietf.org/html/rfc7541#section-4.1),
+    /// that will be added to the dynamic table. Larger headers are sent as literals without indexing, as per
+    /// [IETF RFC 7541 Section 6.2.2](https://tools.ietf.org/html/rfc7541#section-6.2.2), so a single oversized value cannot evict
+    /// the smaller, repetitive headers.
+    ///
+    /// ## Arguments
+    ///
+    /// * threshold - the largest entry size in bytes to index, or None to index every header
+    pub fn set_index_size_threshold(&mut self, threshold: Option<usize>) {
+        self.index_size_threshold = threshold;
+    }
+
+    /// Function used to encode a list of headers into a header block, using indexed representations where the
+    /// header is already in the static or dynamic table, and literals with incremental indexing otherwise.
+    ///
+    /// ## Arguments
+    ///
+    /// * headers - the name and value pairs to encode, in order
+    ///
+    /// ## Returns
+    ///
+    /// * Result<Vec<u8>,&'static str> - the encoded header block or an error message
+    pub fn encode(&mut self, headers: &[(&str, &str)]) -> Result<Vec<u8>, &'static str>{
+        let mut block = match self.pending_size_update.take() {
+            Some((smallest, size)) if smallest < size => {
+                let mut block = new_size_update(smallest as u32);
+                block.extend(new_size_update(size as u32));
+                block
+            },
+            Some((_, size)) => new_size_update(size as u32),
+            None => Vec::new(),
+        };
+
+        for (name, value) in headers {
+            let (exact, name_index) = self.find(name, value);
+            let oversized = self.index_size_threshold.is_some_and(|x| name.len() + value.len() + 32 > x);
+            match (exact, name_index) {
+                (Some(index), _) => block.extend(new_indexed(index)?),
+                (None, Some(index)) if oversized => block.extend(not_indexed(new_literal(value, index, None, false)?)),
+                (None, None) if oversized => block.extend(not_indexed(new_literal(value, 0, Some(name), false)?)),
+                (None, Some(index)) => {
+                    block.extend(new_literal(value, index, None, false)?);
+                    let _ = self.dynamic_table.add((String::from(*name), String::from(*value)));
+                },
+                (None, None) => {
+                    block.extend(new_literal(value, 0, Some(name), false)?);
+                    let _ = self.dynamic_table.add((String::from(*name), String::from(*value)));
+                },
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Function that searches the static table and then the dynamic table for a header
+    ///
+    /// ## Returns
+    ///
+    /// * (Option<u32>, Option<u32>) - the index of an exact match, and the index of the first entry matching only the name
+    fn find(&self, name: &str, value: &str) -> (Option<u32>, Option<u32>) {
+        let mut name_index = None;
+
+        for (i, entry) in STATIC_TABLE.iter().enumerate() {
+            if entry.0 == name {
+                if entry.1 == value {
+                    return (Some(i as u32 + 1), None);
+                }
+                name_index = name_index.or(Some(i as u32 + 1));
+            }
+        }
+
+        let dynamic_index = |i: usize| (STATIC_TABLE.len() + 1 + i) as u32;
+        if let Some(i) = self.dynamic_table.contains(name, value) {
+            return (Some(dynamic_index(i)), None);
+        }
+
+        (None, name_index.or_else(|| self.dynamic_table.find_name(name).map(dynamic_index)))
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    #[test]
+    fn test_encode_static_indexed(){
+        let mut encoder = Encoder::new(128);
+
+        assert_eq!(vec![130_u8, 132_u8], encoder.encode(&[(":method", "GET"), (":path", "/")]).unwrap());
+    }
+
+    #[test]
+    fn test_encode_literal_indexed_name(){
+        let mut encoder = Encoder::new(128);
+
+        assert_eq!(vec![79_u8, 3_u8, 0x73, 0x65, 0x74], encoder.encode(&[("accept-charset", "set")]).unwrap());
+    }
+
+    #[test]
+    fn test_encode_reuses_dynamic_table(){
+        let mut encoder = Encoder::new(128);
+
+        encoder.encode(&[("key", "abc")]).unwrap();
+
+        assert_eq!(vec![190_u8], encoder.encode(&[("key", "abc")]).unwrap());
+    }
+
+    #[test]
+    fn test_encode_emits_size_update(){
+        let mut encoder = Encoder::new(128);
+        encoder.set_table_size(64);
+
+        assert_eq!(vec![63_u8, 33_u8, 130_u8], encoder.encode(&[(":method", "GET")]).unwrap());
+        assert_eq!(vec![130_u8], encoder.encode(&[(":method", "GET")]).unwrap());
+    }
+
+    #[test]
+    fn test_encode_does_not_index_oversized(){
+        let mut encoder = Encoder::new(4096);
+        encoder.set_index_size_threshold(Some(40));
+
+        assert_eq!(vec![15_u8, 17_u8, 3_u8, 0x61, 0x62, 0x63], encoder.encode(&[("cookie", "abc")]).unwrap());
+        assert_eq!(vec![64_u8, 1_u8, 0x61, 1_u8, 0x62], encoder.encode(&[("a", "b")]).unwrap());
+        assert_eq!(None, encoder.dynamic_table.find_name("cookie"));
+        assert_eq!(Some(0), encoder.dynamic_table.find_name("a"));
+    }
+
+    #[test]
+    fn test_encode_emits_smallest_size_update_first(){
+        let mut encoder = Encoder::new(128);
+        encoder.set_table_size(0);
+        encoder.set_table_size(4096);
+
+        assert_eq!(vec![32_u8, 63_u8, 225_u8, 31_u8, 130_u8], encoder.encode(&[(":method", "GET")]).unwrap());
+    }
+}
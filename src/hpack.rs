@@ -1,10 +1,15 @@
-use crate::dyn_table::DynamicTable;
+use crate::dyn_table::{DynamicTable, Table};
 use crate::decode_int;
+use crate::huffman;
 use lazy_static::lazy_static;
+use std::fmt;
+use std::ops::Deref;
 use std::str;
 
-pub struct Hpack{
-    dynamic_table: DynamicTable,
+pub struct Hpack<T: Table = DynamicTable>{
+    dynamic_table: T,
+    max_table_size: usize,
+    max_header_list_size: Option<usize>,
 }
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
@@ -13,9 +18,204 @@ pub struct Header {
     indexed: bool
 }
 
+/// An owned list of decoded headers, kept in the order they appeared in the header block.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct HeaderList(Vec<Header>);
+
+/// Display adapter that prints a run of headers as `name: value` lines, replacing the value of
+/// any header the redaction hook selects. Built through `display_headers`, `display_headers_with`, `Header::display_with`
+/// or `HeaderList::display_with`.
+pub struct HeaderDisplay<'a, F> {
+    headers: &'a [Header],
+    redact: F,
+}
+
+static REDACTED: &str = "[redacted]";
+pub(crate) static ERROR_HEADER_LIST_SIZE: &str = "Header list exceeds maximum header list size";
+
+impl Header {
+    /// Function that returns the name of the header
+    pub fn name(&self) -> &str {
+        &self.value.0
+    }
+
+    /// Function that returns the value of the header
+    pub fn value(&self) -> &str {
+        &self.value.1
+    }
+
+    /// Function that returns true if the header was sent as never indexed, which marks its value as sensitive
+    /// as per [IETF RFC 7541 Section 7.1.3](https://tools.ietf.org/html/rfc7541#section-7.1.3)
+    pub fn is_sensitive(&self) -> bool {
+        !self.indexed
+    }
+
+    /// Function that builds a display adapter for this header using a custom redaction hook
+    ///
+    /// ## Arguments
+    ///
+    /// * redact - a closure returning true for any header whose value should be hidden
+    ///
+    /// ## Returns
+    ///
+    /// * HeaderDisplay - an adapter implementing Display as `name: value`
+    pub fn display_with<F: Fn(&Header) -> bool>(&self, redact: F) -> HeaderDisplay<'_, F> {
+        HeaderDisplay{headers: std::slice::from_ref(self), redact}
+    }
+}
+
+/// Prints the header as `name: value`, redacting the value if the header is sensitive
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(Header::is_sensitive).fmt(f)
+    }
+}
+
+impl HeaderList {
+    /// Function that builds a display adapter for the list using a custom redaction hook
+    ///
+    /// ## Arguments
+    ///
+    /// * redact - a closure returning true for any header whose value should be hidden
+    ///
+    /// ## Returns
+    ///
+    /// * HeaderDisplay - an adapter implementing Display with one `name: value` line per header
+    pub fn display_with<F: Fn(&Header) -> bool>(&self, redact: F) -> HeaderDisplay<'_, F> {
+        display_headers_with(&self.0, redact)
+    }
+
+    /// Function that consumes the list, returning the underlying vector of headers
+    pub fn into_inner(self) -> Vec<Header> {
+        self.0
+    }
+}
+
+impl From<Vec<Header>> for HeaderList {
+    fn from(headers: Vec<Header>) -> HeaderList {
+        HeaderList(headers)
+    }
+}
+
+impl Deref for HeaderList {
+    type Target = [Header];
+
+    fn deref(&self) -> &[Header] {
+        &self.0
+    }
+}
+
+impl IntoIterator for HeaderList {
+    type Item = Header;
+    type IntoIter = std::vec::IntoIter<Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a HeaderList {
+    type Item = &'a Header;
+    type IntoIter = std::slice::Iter<'a, Header>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Prints one `name: value` line per header, redacting the values of sensitive headers
+impl fmt::Display for HeaderList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(Header::is_sensitive).fmt(f)
+    }
+}
+
+impl<'a, F: Fn(&Header) -> bool> fmt::Display for HeaderDisplay<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, header) in self.headers.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let value = if (self.redact)(header) { REDACTED } else { header.value() };
+            write!(f, "{}: {}", header.name(), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Function that builds a display adapter for a borrowed run of headers, redacting the values of sensitive headers,
+/// so a block returned by `Hpack::read_headers` can be logged without moving it into a `HeaderList`
+///
+/// ## Arguments
+///
+/// * headers - the decoded headers to display
+///
+/// ## Returns
+///
+/// * HeaderDisplay - an adapter implementing Display with one `name: value` line per header
+pub fn display_headers(headers: &[Header]) -> HeaderDisplay<'_, fn(&Header) -> bool> {
+    display_headers_with(headers, Header::is_sensitive)
+}
+
+/// Function that builds a display adapter for a borrowed run of headers using a custom redaction hook
+///
+/// ## Arguments
+///
+/// * headers - the decoded headers to display
+/// * redact - a closure returning true for any header whose value should be hidden
+///
+/// ## Returns
+///
+/// * HeaderDisplay - an adapter implementing Display with one `name: value` line per header
+pub fn display_headers_with<F: Fn(&Header) -> bool>(headers: &[Header], redact: F) -> HeaderDisplay<'_, F> {
+    HeaderDisplay{headers, redact}
+}
+
+/// Function that borrows decoded headers as the name and value pairs the `pseudo` validators take
+pub(crate) fn as_pairs(headers: &[Header]) -> Vec<(&str, &str)> {
+    headers.iter().map(|h| (h.name(), h.value())).collect()
+}
+
 impl Hpack{
     pub fn new(dynamic_table_size: usize) -> Hpack{
-        Hpack{dynamic_table: DynamicTable::new(dynamic_table_size)}
+        Hpack::with_table(DynamicTable::new(dynamic_table_size), dynamic_table_size)
+    }
+}
+
+impl<T: Table> Hpack<T>{
+    /// Builds a new decoder on top of the given dynamic table implementation
+    ///
+    /// ## Arguments
+    ///
+    /// * dynamic_table - the table to decode against
+    /// * max_table_size - the largest table size the peer may select with a Dynamic Table Size Update
+    pub fn with_table(dynamic_table: T, max_table_size: usize) -> Hpack<T>{
+        Hpack{dynamic_table, max_table_size, max_header_list_size: None}
+    }
+
+    /// Function that returns the dynamic table used for decoding
+    pub fn table(&self) -> &T {
+        &self.dynamic_table
+    }
+
+    /// Function used to change the largest table size the peer may select. Entries are only evicted once the peer
+    /// acknowledges the change with a Dynamic Table Size Update as per [IETF RFC 7541 Section 4.2](https://tools.ietf.org/html/rfc7541#section-4.2)
+    ///
+    /// ## Arguments
+    ///
+    /// * size - the new maximum size in bytes of the dynamic table
+    pub fn set_max_table_size(&mut self, size: usize) {
+        self.max_table_size = size;
+    }
+
+    /// Function used to limit the size of the header lists read, as measured by [IETF RFC 7540 Section 6.5.2](https://tools.ietf.org/html/rfc7540#section-6.5.2).
+    /// The limit is checked as each header is decoded, so an oversized block is rejected before it is fully read.
+    ///
+    /// ## Arguments
+    ///
+    /// * size - the largest header list size in bytes, or None for no limit
+    pub fn set_max_header_list_size(&mut self, size: Option<usize>) {
+        self.max_header_list_size = size;
     }
 
     ///Function used to read in a stream of headers, and convert them into a list of headers for consumption. 
@@ -29,122 +229,148 @@ impl Hpack{
     ///* Result<Vec<Header>,&'static str> - A vector of Header objects or an error message 
     /// 
     pub fn read_headers(&mut self, stream: Vec<u8>) -> Result<Vec<Header>,&'static str>{
-        match stream.get(0) {  
-            Some(x) => {
-                if (x >> 7) == 1_u8 {
-                    self.process_indexed(stream)
-                }else if (x >> 6) == 1_u8{
-                    self.process_indexed_literal(stream)
-                }else if (x >> 5) == 1_u8{
-                    let (size, stream) = decode_int(stream, 5);
-                    self.dynamic_table.set_size(size as usize);
-                    self.read_headers(stream)
-                }else if (x >> 4) == 0_u8 {
-                    self.process_non_indexed_literal(stream)
-                }else if (x >> 4) == 1_u8 {
-                    self.process_never_indexed_literal(stream)
-                }else {
-                    Err("Invalid start of header")
+        let mut headers = Vec::new();
+        let mut list_size = 0;
+        let mut stream = stream.as_slice();
+
+        while let Some(&x) = stream.first() {
+            let (header, rest) = if (x >> 7) == 1_u8 {
+                self.process_indexed(stream)?
+            }else if (x >> 6) == 1_u8{
+                self.process_indexed_literal(stream)?
+            }else if (x >> 5) == 1_u8{
+                let (size, rest) = decode_int(stream, 5)?;
+                if size as usize > self.max_table_size {
+                    return Err("Error size update exceeds maximum table size");
                 }
-            },
-            None => Ok(Vec::new()),
+                self.dynamic_table.set_size(size as usize);
+                stream = rest;
+                continue;
+            }else if (x >> 4) == 0_u8 {
+                self.process_non_indexed_literal(stream)?
+            }else if (x >> 4) == 1_u8 {
+                self.process_never_indexed_literal(stream)?
+            }else {
+                return Err("Invalid start of header");
+            };
+
+            list_size += header.name().len() + header.value().len() + 32;
+            if self.max_header_list_size.is_some_and(|max| list_size > max) {
+                return Err(ERROR_HEADER_LIST_SIZE);
+            }
+            headers.push(header);
+            stream = rest;
         }
+
+        Ok(headers)
     }
 
     ///Function used to process an indexed refrence to a header from the static or dynamic table
     /// 
     /// ## Arguments
     /// 
-    /// * stream - the vector of bytes to be consumed by the method 
-    fn process_indexed(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str> {
-        let (int, stream) = decode_int(stream, 7);
-        let mut vec = self.read_headers(stream)?;
-        vec.insert(0, Header{value: self.get_static_entry_from_index(int)?, indexed: true});
-        Ok(vec)
+    /// * stream - the bytes to be consumed by the method 
+    fn process_indexed<'a>(&mut self, stream: &'a [u8]) -> Result<(Header, &'a [u8]), &'static str> {
+        let (int, stream) = decode_int(stream, 7)?;
+        let header = Header{value: self.get_static_entry_from_index(int)?, indexed: true};
+        Ok((header, stream))
     }
 
-    fn process_indexed_literal(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str> {
-        let (index, stream) = decode_int(stream, 6);
+    fn process_indexed_literal<'a>(&mut self, stream: &'a [u8]) -> Result<(Header, &'a [u8]), &'static str> {
+        let (index, stream) = decode_int(stream, 6)?;
         
         if index == 0 {
-            self.process_literial_with_name(stream, true)
+            self.process_literial_with_name(stream, true, true)
         } else {
-            self.process_literal_with_index(stream, index, true)
+            self.process_literal_with_index(stream, index, true, true)
         }
     }
 
-    fn process_non_indexed_literal(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str> {
-        let (index, stream) = decode_int(stream, 4);
+    fn process_non_indexed_literal<'a>(&mut self, stream: &'a [u8]) -> Result<(Header, &'a [u8]), &'static str> {
+        let (index, stream) = decode_int(stream, 4)?;
 
-         if index == 0 {
-            self.process_literial_with_name(stream, true)
+        if index == 0 {
+            self.process_literial_with_name(stream, false, true)
         } else {
-            self.process_literal_with_index(stream, index, true)
+            self.process_literal_with_index(stream, index, false, true)
         }
     }
 
-    fn process_never_indexed_literal(&mut self, stream: Vec<u8>) -> Result<Vec<Header>, &'static str> {
-        let (index, stream) = decode_int(stream, 4);
+    fn process_never_indexed_literal<'a>(&mut self, stream: &'a [u8]) -> Result<(Header, &'a [u8]), &'static str> {
+        let (index, stream) = decode_int(stream, 4)?;
 
         if index == 0 {
-            self.process_literial_with_name(stream, false)
+            self.process_literial_with_name(stream, false, false)
         } else {
-            self.process_literal_with_index(stream, index, false)
+            self.process_literal_with_index(stream, index, false, false)
         }
     }
 
-    fn get_string(stream: Vec<u8>) -> (Vec<u8>, String){
-        let (length, mut stream) = decode_int(stream, 7);
+    fn get_string(stream: &[u8]) -> Result<(&[u8], String), &'static str>{
+        let huffman = stream.first().is_some_and(|x| (x >> 7) == 1_u8);
+        let (length, stream) = decode_int(stream, 7)?;
             let range = length as usize;
+            if range > stream.len() {
+                return Err("Error string length exceeds header block");
+            }
 
-            let value = match str::from_utf8(&stream.as_slice()[..range]) {
-                Ok(x) => String::from(x),
-                Err(_) => String::from("invalid utf8"),
+            let bytes = if huffman {
+                huffman::decode(&stream[..range])?
+            } else {
+                stream[..range].to_vec()
             };
 
-            for _ in 0..length {
-                stream.remove(0);
-            }
+            let value = match String::from_utf8(bytes) {
+                Ok(x) => x,
+                Err(_) => String::from("invalid utf8"),
+            };
 
-            (stream, value)
+            Ok((&stream[range..], value))
     }
 
-    fn process_literial_with_name(&mut self, stream: Vec<u8>, indexed: bool) -> Result<Vec<Header>, &'static str> {
-        let (stream, name) = Hpack::get_string(stream);
-        let (stream, value) = Hpack::get_string(stream);
-
-        let header = (name, String::from(value));
-        if indexed {self.dynamic_table.add(header.clone());}
+    fn process_literial_with_name<'a>(&mut self, stream: &'a [u8], add: bool, indexed: bool) -> Result<(Header, &'a [u8]), &'static str> {
+        let (stream, name) = Self::get_string(stream)?;
+        let (stream, value) = Self::get_string(stream)?;
 
-        let mut vec = self.read_headers(stream)?;
-        vec.insert(0, Header{ value:header , indexed: indexed});
+        let header = (name, value);
+        if add {let _ = self.dynamic_table.add(header.clone());}
 
-        Ok(vec)
+        Ok((Header{ value:header , indexed}, stream))
     }
 
-    fn process_literal_with_index(&mut self, stream: Vec<u8>, index: u32, indexed: bool) -> Result<Vec<Header>, &'static str> {
-        let (stream, value) = Hpack::get_string(stream);
+    fn process_literal_with_index<'a>(&mut self, stream: &'a [u8], index: u32, add: bool, indexed: bool) -> Result<(Header, &'a [u8]), &'static str> {
+        let (stream, value) = Self::get_string(stream)?;
 
         let mut header = self.get_static_entry_from_index(index)?.clone();
         header.1 = value;
-        if indexed {self.dynamic_table.add(header.clone());}
+        if add {let _ = self.dynamic_table.add(header.clone());}
 
-        let mut vec = self.read_headers(stream)?;
-
-        vec.insert(0, Header{value: header, indexed: indexed});
-        
-        Ok(vec)
+        Ok((Header{value: header, indexed}, stream))
     }
 
     fn get_static_entry_from_index(&self, i: u32) -> Result<(String,String), &'static str> {
-        if i < 62 {
-            match STATIC_TABLE.get((i-1) as usize) {
-                Some(x) => Ok((String::from(x.0),String::from(x.1))),
-                None => Err("Error i is 0"),
-            }
+        let (name, value) = self.resolve_index(i)?;
+        Ok((String::from(name), String::from(value)))
+    }
+
+    /// Function used to resolve an index against the static table and the current state of the dynamic table
+    /// as per [IETF RFC 7541 Section 2.3.3](https://tools.ietf.org/html/rfc7541#section-2.3.3)
+    ///
+    /// ## Arguments
+    ///
+    /// * i - the index to resolve, starting at 1
+    ///
+    /// ## Returns
+    ///
+    /// * Result<(&str, &str), &'static str> - the name and value of the entry or an error message
+    pub fn resolve_index(&self, i: u32) -> Result<(&str, &str), &'static str> {
+        if i == 0 {
+            Err("Error i is 0")
+        } else if i < 62 {
+            Ok(STATIC_TABLE[(i - 1) as usize])
         } else {
-            match self.dynamic_table.get(((i - 62) - 1) as usize){
-                Some(x) => Ok((x.0.clone(), (x.1.clone()))),
+            match self.dynamic_table.get((i - 62) as usize){
+                Some(x) => Ok(x),
                 None => Err("Error index outside of dynamic table space"),
             }
         }
@@ -153,69 +379,70 @@ impl Hpack{
 
 lazy_static! {
     ///Static header list as defined by [IETF RFC 7541 Section 5.1](https://tools.ietf.org/html/rfc7541#appendix-A)
-    static ref STATIC_TABLE: Vec<(&'static str,&'static str)> = {
-        let mut table = Vec::new();
-        table.push((":authority",""));
-        table.push((":method","GET"));
-        table.push((":method","POST"));
-        table.push((":path","/"));
-        table.push((":path","/index.html"));
-        table.push((":scheme","http"));
-        table.push((":scheme","https"));
-        table.push((":status","200"));
-        table.push((":status","204"));
-        table.push((":status","206"));
-        table.push((":status","304"));
-        table.push((":status","400"));
-        table.push((":status","404"));
-        table.push((":status","500"));
-        table.push(("accept-charset",""));
-        table.push(("accept-encoding","gzip,deflate"));
-        table.push(("accept-language",""));
-        table.push(("accept-ranges",""));
-        table.push(("accept",""));
-        table.push(("access-control-allow-origin",""));
-        table.push(("age",""));
-        table.push(("allow",""));
-        table.push(("authorization",""));
-        table.push(("cache-control",""));
-        table.push(("content-disposition",""));
-        table.push(("content-encoding",""));
-        table.push(("content-language",""));
-        table.push(("content-length",""));
-        table.push(("content-location",""));
-        table.push(("contant-range",""));
-        table.push(("content-type",""));
-        table.push(("cookie",""));
-        table.push(("date",""));
-        table.push(("etag",""));
-        table.push(("expect",""));
-        table.push(("expires",""));
-        table.push(("from",""));
-        table.push(("host",""));
-        table.push(("if-match",""));
-        table.push(("if-modified-since",""));
-        table.push(("if-none-match",""));
-        table.push(("if-range",""));
-        table.push(("if-unmodified-since",""));
-        table.push(("last-modified",""));
-        table.push(("link",""));
-        table.push(("location",""));
-        table.push(("max-forwards",""));
-        table.push(("proxy-authenticate",""));
-        table.push(("proxy-authorization",""));
-        table.push(("range",""));
-        table.push(("referer",""));
-        table.push(("refresh",""));
-        table.push(("retry-after",""));
-        table.push(("server",""));
-        table.push(("set-cookie",""));
-        table.push(("strict-transport-security",""));
-        table.push(("transfer-encoding",""));
-        table.push(("user-agent",""));
-        table.push(("vary",""));
-        table.push(("via",""));
-        table.push(("www-authenticate",""));
+    pub(crate) static ref STATIC_TABLE: Vec<(&'static str,&'static str)> = {
+        let table = vec![
+            (":authority",""),
+            (":method","GET"),
+            (":method","POST"),
+            (":path","/"),
+            (":path","/index.html"),
+            (":scheme","http"),
+            (":scheme","https"),
+            (":status","200"),
+            (":status","204"),
+            (":status","206"),
+            (":status","304"),
+            (":status","400"),
+            (":status","404"),
+            (":status","500"),
+            ("accept-charset",""),
+            ("accept-encoding","gzip,deflate"),
+            ("accept-language",""),
+            ("accept-ranges",""),
+            ("accept",""),
+            ("access-control-allow-origin",""),
+            ("age",""),
+            ("allow",""),
+            ("authorization",""),
+            ("cache-control",""),
+            ("content-disposition",""),
+            ("content-encoding",""),
+            ("content-language",""),
+            ("content-length",""),
+            ("content-location",""),
+            ("content-range",""),
+            ("content-type",""),
+            ("cookie",""),
+            ("date",""),
+            ("etag",""),
+            ("expect",""),
+            ("expires",""),
+            ("from",""),
+            ("host",""),
+            ("if-match",""),
+            ("if-modified-since",""),
+            ("if-none-match",""),
+            ("if-range",""),
+            ("if-unmodified-since",""),
+            ("last-modified",""),
+            ("link",""),
+            ("location",""),
+            ("max-forwards",""),
+            ("proxy-authenticate",""),
+            ("proxy-authorization",""),
+            ("range",""),
+            ("referer",""),
+            ("refresh",""),
+            ("retry-after",""),
+            ("server",""),
+            ("set-cookie",""),
+            ("strict-transport-security",""),
+            ("transfer-encoding",""),
+            ("user-agent",""),
+            ("vary",""),
+            ("via",""),
+            ("www-authenticate",""),
+        ];
         table
     };
 }
@@ -272,9 +499,9 @@ mod test{
 
         let header_1 = Header{value: (String::from(":method"),String::from("GET")), indexed: true};
 
-        hpack.read_headers(stream);
+        hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8];
+        let stream = vec![191_u8];
         let expected = vec![header_1.clone()];
 
         assert_eq!(expected,hpack.read_headers(stream).unwrap());
@@ -289,9 +516,9 @@ mod test{
         let header_1 = Header{value: (String::from(":method"),String::from("GET")), indexed: true};
         let header_2 = Header{value: (String::from("accept-charset"),String::from("set")), indexed: true};
 
-        hpack.read_headers(stream);
+        hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8, 191_u8];
+        let stream = vec![191_u8, 190_u8];
         let expected = vec![header_1.clone(), header_2.clone()];
 
         assert_eq!(expected,hpack.read_headers(stream).unwrap());
@@ -324,9 +551,9 @@ mod test{
     fn test_read_headers_literial_not_indexed_dosent_get_indexed(){
         let mut hpack = Hpack::new(128);
         let stream = vec![2_u8, 3_u8, 0x47, 0x45, 0x54];
-        hpack.read_headers(stream);
+        hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8];
+        let stream = vec![190_u8];
 
         assert_eq!("Error index outside of dynamic table space", hpack.read_headers(stream).unwrap_err());
     }
@@ -335,9 +562,9 @@ mod test{
     fn test_read_headers_literial_not_indexed_dosent_get_indexed_with_name(){
         let mut hpack = Hpack::new(128);
         let stream = vec![0_u8, 7_u8, 0x3a, 0x6d, 0x65, 0x74, 0x68, 0x6f, 0x64, 3_u8, 0x47, 0x45, 0x54];
-        hpack.read_headers(stream);
+        hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8];
+        let stream = vec![190_u8];
 
         assert_eq!("Error index outside of dynamic table space", hpack.read_headers(stream).unwrap_err());
     }
@@ -370,9 +597,9 @@ mod test{
     fn test_read_headers_literial_never_indexed_dosent_get_indexed(){
         let mut hpack = Hpack::new(128);
         let stream = vec![18_u8, 3_u8, 0x47, 0x45, 0x54];
-        hpack.read_headers(stream);
+        hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8];
+        let stream = vec![190_u8];
 
         assert_eq!("Error index outside of dynamic table space", hpack.read_headers(stream).unwrap_err());
     }
@@ -383,14 +610,14 @@ mod test{
         let stream = vec![16_u8, 7_u8, 0x3a, 0x6d, 0x65, 0x74, 0x68, 0x6f, 0x64, 3_u8, 0x47, 0x45, 0x54];
         hpack.read_headers(stream).unwrap();
 
-        let stream = vec![192_u8];
+        let stream = vec![190_u8];
 
         assert_eq!("Error index outside of dynamic table space", hpack.read_headers(stream).unwrap_err());
     }
 
     #[test]
     fn test_change_table_size(){
-        let mut hpack = Hpack::new(128);
+        let mut hpack = Hpack::new(4096);
         let stream = vec![63_u8, 154_u8, 10_u8, 2_u8, 3_u8, 0x47, 0x45, 0x54];
         let header_1 = Header{value: (String::from(":method"),String::from("GET")), indexed: true};
         let expected = vec![header_1.clone()];
@@ -398,4 +625,126 @@ mod test{
         assert_eq!(expected,hpack.read_headers(stream).unwrap());
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_display_header(){
+        let header = Header{value: (String::from(":method"),String::from("GET")), indexed: true};
+
+        assert_eq!(":method: GET", header.to_string());
+    }
+
+    #[test]
+    fn test_display_header_redacts_never_indexed(){
+        let header = Header{value: (String::from("authorization"),String::from("secret")), indexed: false};
+
+        assert_eq!("authorization: [redacted]", header.to_string());
+        assert_eq!("authorization: secret", header.display_with(|_| false).to_string());
+    }
+
+    #[test]
+    fn test_display_header_list(){
+        let mut hpack = Hpack::new(128);
+        let stream = vec![130_u8, 132_u8, 16_u8, 3_u8, 0x6b, 0x65, 0x79, 3_u8, 0x61, 0x62, 0x63];
+        let list = HeaderList::from(hpack.read_headers(stream).unwrap());
+
+        assert_eq!(":method: GET\n:path: /\nkey: [redacted]", list.to_string());
+        assert_eq!(":method: GET\n:path: [redacted]\nkey: abc", list.display_with(|h| h.name() == ":path").to_string());
+    }
+
+    #[test]
+    fn test_resolve_index(){
+        let mut hpack = Hpack::new(128);
+        hpack.read_headers(vec![64_u8, 3_u8, 0x6b, 0x65, 0x79, 3_u8, 0x61, 0x62, 0x63]).unwrap();
+
+        assert_eq!((":method", "GET"), hpack.resolve_index(2).unwrap());
+        assert_eq!(("key", "abc"), hpack.resolve_index(62).unwrap());
+        assert_eq!("Error i is 0", hpack.resolve_index(0).unwrap_err());
+        assert_eq!("Error index outside of dynamic table space", hpack.resolve_index(63).unwrap_err());
+    }
+
+    #[test]
+    fn test_read_headers_huffman_literal(){
+        let mut hpack = Hpack::new(128);
+
+        let stream = vec![65_u8, 140_u8, 0xf1, 0xe3, 0xc2, 0xe5, 0xf2, 0x3a, 0x6b, 0xa0, 0xab, 0x90, 0xf4, 0xff];
+
+        let header_1 = Header{value: (String::from(":authority"),String::from("www.example.com")), indexed: true};
+
+        assert_eq!(vec![header_1], hpack.read_headers(stream).unwrap());
+    }
+
+    struct CountingTable{
+        table: DynamicTable,
+        adds: usize,
+    }
+
+    impl Table for CountingTable{
+        fn get(&self, index: usize) -> Option<(&str, &str)> {
+            Table::get(&self.table, index)
+        }
+
+        fn add(&mut self, header: (String, String)) -> Result<(), &'static str> {
+            self.adds += 1;
+            self.table.add(header)
+        }
+
+        fn set_size(&mut self, new_size: usize) {
+            self.table.set_size(new_size)
+        }
+
+        fn len(&self) -> usize {
+            self.table.len()
+        }
+
+        fn size(&self) -> usize {
+            self.table.size()
+        }
+    }
+
+    #[test]
+    fn test_read_headers_custom_table(){
+        let mut hpack = Hpack::with_table(CountingTable{table: DynamicTable::new(128), adds: 0}, 128);
+
+        let stream = vec![66_u8, 3_u8, 0x47, 0x45, 0x54, 79_u8, 3_u8, 0x73, 0x65, 0x74];
+        hpack.read_headers(stream).unwrap();
+
+        assert_eq!(2, hpack.table().adds);
+        assert_eq!(Some(0), hpack.table().find_name("accept-charset"));
+    }
+
+    #[test]
+    fn test_size_update_exceeds_maximum(){
+        let mut hpack = Hpack::new(128);
+
+        assert_eq!("Error size update exceeds maximum table size", hpack.read_headers(vec![0x3f, 0xe1, 0x1f]).unwrap_err());
+        assert!(hpack.read_headers(vec![0x3f, 0x61]).is_ok());
+    }
+
+    #[test]
+    fn test_read_headers_large_block(){
+        let mut hpack = Hpack::new(4096);
+
+        let headers = hpack.read_headers(vec![0x82; 65536]).unwrap();
+
+        assert_eq!(65536, headers.len());
+        assert!(headers.iter().all(|h| h.name() == ":method" && h.value() == "GET"));
+    }
+
+    #[test]
+    fn test_read_headers_max_header_list_size(){
+        let mut hpack = Hpack::new(4096);
+        hpack.set_max_header_list_size(Some(84));
+
+        assert_eq!(2, hpack.read_headers(vec![0x82, 0x82]).unwrap().len());
+        assert_eq!(ERROR_HEADER_LIST_SIZE, hpack.read_headers(vec![0x82; 65536]).unwrap_err());
+    }
+
+    #[test]
+    fn test_display_headers_borrowed(){
+        let mut hpack = Hpack::new(4096);
+        let headers = hpack.read_headers(vec![130_u8, 16_u8, 3_u8, 0x6b, 0x65, 0x79, 3_u8, 0x61, 0x62, 0x63]).unwrap();
+
+        assert_eq!(":method: GET\nkey: [redacted]", display_headers(&headers).to_string());
+        assert_eq!(":method: [redacted]\nkey: abc", display_headers_with(&headers, |h| h.name() == ":method").to_string());
+        assert_eq!(2, headers.len());
+    }
+}
@@ -2,6 +2,16 @@ use std::str;
 
 pub mod hpack;
 pub mod dyn_table;
+pub mod encoder;
+pub mod connection;
+pub mod text;
+pub mod huffman;
+pub mod pseudo;
+pub mod message;
+#[cfg(feature = "alloc-count")]
+pub mod alloc_count;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 /// Function that returns a new Indexed Header Field Representation as per [IETF RFC 7541 Section 6.1](https://tools.ietf.org/html/rfc7541#section-6.1)
 /// 
@@ -32,26 +42,19 @@ pub fn new_indexed(number: u32) -> Result<Vec<u8>,&'static str>{
 /// ## Returns
 /// 
 ///  * Result<Vec<u8>,&'static str> - a result containing the Vector of bytes or an error string
-pub fn new_literal(value: &str, index: u32, name: Option<&str>, _huffman: bool) -> Result<Vec<u8>, &'static str>{
+pub fn new_literal(value: &str, index: u32, name: Option<&str>, huffman: bool) -> Result<Vec<u8>, &'static str>{
     let build_literal = |index, value: &str| {
         if index == 0 {
             Err(ERROR_INDEX_ZERO)
         }else{
-            let mut payload = encode_int(7, value.len() as u32,
-                            mask_first_byte(encode_int(6, index, Vec::new()), 64_u8));
-            payload.extend_from_slice(value.as_bytes());
-            
-            Ok(payload)
+            Ok(encode_string(value, huffman, mask_first_byte(encode_int(6, index, Vec::new()), 64_u8)))
         }
     };
 
     let build_literal_with_name = |name: &str, value: &str| {
-        let mut payload = encode_int(7, name.len() as u32, vec![64_u8]);
-        payload.extend_from_slice(name.as_bytes());
-        payload = encode_int(7, value.len() as u32, payload);
-        payload.extend_from_slice(value.as_bytes());
+        let payload = encode_string(name, huffman, vec![64_u8]);
 
-        Ok(payload)
+        Ok(encode_string(value, huffman, payload))
     };
 
     match name {
@@ -60,6 +63,43 @@ pub fn new_literal(value: &str, index: u32, name: Option<&str>, _huffman: bool)
     }
 }
 
+/// Function that encodes a String Literal as per [IETF RFC 7541 Section 5.2](https://tools.ietf.org/html/rfc7541#section-5.2)
+/// 
+/// ## Arguments 
+/// * value - the string to be encoded
+/// * huffman - a boolean value representing if the string should be huffman encoded or not
+/// * vec - a vector to store the string in, appends to the end of the vector
+/// 
+/// ## Returns
+/// * Vec<u8> - a vector with the length prefixed string appended
+fn encode_string(value: &str, huffman: bool, vec: Vec<u8>) -> Vec<u8> {
+    if huffman {
+        let encoded = huffman::encode(value.as_bytes());
+        let length = vec.len();
+        let mut payload = encode_int(7, encoded.len() as u32, vec);
+        payload[length] |= 128_u8;
+        payload.extend_from_slice(&encoded);
+        payload
+    }else{
+        let mut payload = encode_int(7, value.len() as u32, vec);
+        payload.extend_from_slice(value.as_bytes());
+        payload
+    }
+}
+
+/// Function that returns a new Dynamic Table Size Update as per [IETF RFC 7541 Section 6.3](https://tools.ietf.org/html/rfc7541#section-6.3)
+/// 
+/// ## Arguments 
+/// 
+/// * size - the new maximum size in bytes of the dynamic table
+/// 
+/// ## Returns 
+/// 
+/// * Vec<u8> - the encoded size update
+pub fn new_size_update(size: u32) -> Vec<u8>{
+    mask_first_byte(encode_int(5, size, Vec::new()), 32_u8)
+}
+
 /// Function that takes a Literal field and sets it to not be indexed 
 /// 
 /// ## Arguments
@@ -68,9 +108,9 @@ pub fn new_literal(value: &str, index: u32, name: Option<&str>, _huffman: bool)
 /// ## Returns
 /// * Vec<u8> - a Literal field that is not indexed
 pub fn not_indexed(vec: Vec<u8>) -> Vec<u8>{
-    let (int,mut vec) = decode_int(vec, 6);
+    let (int, rest) = decode_int(&vec, 6).expect("Literal field must start with an index");
     let mut re_encoded = encode_int(4, int, Vec::new());
-    re_encoded.append(&mut vec);
+    re_encoded.extend_from_slice(rest);
 
     re_encoded
 }
@@ -83,9 +123,9 @@ pub fn not_indexed(vec: Vec<u8>) -> Vec<u8>{
 /// ## Returns
 /// * Vec<u8> - a Literal field that is never indexed
 pub fn never_indexed(vec: Vec<u8>) -> Vec<u8>{
-    let (int,mut vec) = decode_int(vec, 6);
+    let (int, rest) = decode_int(&vec, 6).expect("Literal field must start with an index");
     let mut re_encoded =  mask_first_byte(encode_int(4, int, Vec::new()),16_u8);
-    re_encoded.append(&mut vec);
+    re_encoded.extend_from_slice(rest);
 
     re_encoded
 }
@@ -101,14 +141,14 @@ pub fn never_indexed(vec: Vec<u8>) -> Vec<u8>{
 /// * Vec<u8> - a vector with the encoded number appended in bytes with the first byte always having a prefix of ***n*** zeros
 fn encode_int (n: u32, number: u32,vec: Vec<u8>) -> Vec<u8> {
     let mut mut_vec = vec;
-    if number as u32 <= (2_u32.pow(n)) - 1 {
+    if number < 2_u32.pow(n) {
         mut_vec.push(number as u8);
     }else{
         mut_vec = encode_int(n, (2_u32.pow(n)) - 1, mut_vec);
         let mut i = number - (2_u32.pow(n) - 1);
         while i >= 128 {
             mut_vec = encode_int(8, (i % 128) + 128, mut_vec);
-            i = i / 128; 
+            i /= 128; 
         }
         mut_vec = encode_int(8, i, mut_vec);
     }
@@ -116,30 +156,34 @@ fn encode_int (n: u32, number: u32,vec: Vec<u8>) -> Vec<u8> {
     mut_vec
 }
 
-/// Function that takes a stream of bytes represented as vector, and the number of bits encoded on **n** and decodes the integer, returning the number and the remaining byte stream
+/// Function that takes a stream of bytes, and the number of bits encoded on **n** and decodes the integer, returning the number and the remaining byte stream
 /// as per [IETF RFC 7541 Section 5.1](https://tools.ietf.org/html/rfc7541#section-5.1)
 /// 
 /// ## Arguments
-/// * vec - the byte stream vector
+/// * stream - the byte stream, starting at the integer
 /// * n - the encoded integer prefix
 /// 
 /// ## Returns
-/// * (u32, Vec<u8>) - a tuple containing the decoded 32 bit integer, and a vector containing the remaining byte stream
-fn decode_int(vec: Vec<u8>, n: u32) -> (u32, Vec<u8>) {
-    let mut vec = vec;
-    let mut int: u32 = (vec.remove(0) << (8-n) >> (8-n)) as u32;
+/// * Result<(u32, &[u8]),&'static str> - a tuple containing the decoded 32 bit integer and the remaining byte stream, or an error if the stream ends early or the integer exceeds 32 bits
+fn decode_int(stream: &[u8], n: u32) -> Result<(u32, &[u8]), &'static str> {
+    let (first, mut stream) = stream.split_first().ok_or(ERROR_INT_TRUNCATED)?;
+    let mut int: u32 = (first << (8-n) >> (8-n)) as u32;
 
     if int < 2_u32.pow(n) - 1 {
-        (int, vec)
+        Ok((int, stream))
     }else{
         let mut m = 0;
         loop{
-            let b = vec.remove(0);
-            int = int + ((b & 127) as u32 * 2_u32.pow(m));
-            m = m + 7;
+            let (&b, rest) = stream.split_first().ok_or(ERROR_INT_TRUNCATED)?;
+            stream = rest;
+            int = ((b & 127) as u32).checked_shl(m)
+                .filter(|x| x >> m == (b & 127) as u32)
+                .and_then(|x| int.checked_add(x))
+                .ok_or(ERROR_INT_OVERFLOW)?;
+            m += 7;
             if (b & 128) != 128 {break}
         }
-        (int, vec)
+        Ok((int, stream))
     }
 } 
 
@@ -161,6 +205,8 @@ fn mask_first_byte(vec: Vec<u8>, mask: u8) -> Vec<u8> {
 }
 
 static ERROR_INDEX_ZERO: &str = "Error - Indexed field cannot be zero";
+static ERROR_INT_TRUNCATED: &str = "Error - Integer truncated by end of stream";
+static ERROR_INT_OVERFLOW: &str = "Error - Integer exceeds 32 bits";
 
 #[cfg(test)]
 mod tests {
@@ -194,6 +240,13 @@ mod tests {
         assert_eq!(ERROR_INDEX_ZERO, int);
     }
 
+    #[test]
+    fn test_new_size_update(){
+        let update = new_size_update(1337);
+
+        assert_eq!(vec![63_u8, 154_u8, 10_u8], update);
+    }
+
     #[test]
     fn test_new_literal_string(){
         let literal = new_literal("This is 10",1, None, false).unwrap();
@@ -219,25 +272,46 @@ mod tests {
         , literal)
     }
 
+    #[test]
+    fn test_new_literal_huffman(){
+        let literal = new_literal("www.example.com",1, None, true).unwrap();
+
+        assert_eq!(
+            vec![65_u8,140_u8,0xf1,0xe3,0xc2,0xe5,0xf2,0x3a,0x6b,0xa0,0xab,0x90,0xf4,0xff]
+        , literal)
+    }
+
     #[test]
     fn test_decode_fits_in_prefix(){
-        let decoded = decode_int(vec![10_u8], 4);
+        let decoded = decode_int(&[10_u8], 4).unwrap();
 
-        assert_eq!((10,Vec::new()),decoded);
+        assert_eq!((10, &[][..]),decoded);
     }
 
     #[test]
     fn test_decode_larger_then_prefix(){
-        let decoded = decode_int(vec![31_u8, 154_u8, 10_u8], 5);
+        let decoded = decode_int(&[31_u8, 154_u8, 10_u8], 5).unwrap();
 
-        assert_eq!((1337,Vec::new()), decoded);
+        assert_eq!((1337, &[][..]), decoded);
     }
 
     #[test]
     fn test_decode_larger_then_prefix_with_remaining_bytes(){
-         let decoded = decode_int(vec![65_u8,10_u8,0x54,0x68,0x69,0x73,0x20,0x69,0x73,0x20,0x31,0x30], 6);
+         let decoded = decode_int(&[65_u8,10_u8,0x54,0x68,0x69,0x73,0x20,0x69,0x73,0x20,0x31,0x30], 6).unwrap();
+
+        assert_eq!((1, &[10_u8,0x54,0x68,0x69,0x73,0x20,0x69,0x73,0x20,0x31,0x30][..]), decoded);
+    }
 
-        assert_eq!((1,vec![10_u8,0x54,0x68,0x69,0x73,0x20,0x69,0x73,0x20,0x31,0x30]), decoded);
+    #[test]
+    fn test_decode_truncated(){
+        assert_eq!(ERROR_INT_TRUNCATED, decode_int(&[], 7).unwrap_err());
+        assert_eq!(ERROR_INT_TRUNCATED, decode_int(&[31_u8, 154_u8], 5).unwrap_err());
+    }
+
+    #[test]
+    fn test_decode_overflow(){
+        assert_eq!(ERROR_INT_OVERFLOW, decode_int(&[255_u8, 255_u8, 255_u8, 255_u8, 255_u8, 15_u8], 7).unwrap_err());
+        assert_eq!((u32::MAX, &[][..]), decode_int(&[255_u8, 128_u8, 255_u8, 255_u8, 255_u8, 15_u8], 7).unwrap());
     }
 
     #[test]
@@ -0,0 +1,216 @@
+use crate::hpack::{as_pairs, Header, HeaderList};
+use crate::pseudo;
+use std::convert::TryFrom;
+
+static ERROR_INVALID_STATUS: &str = "Error - :status must be a three digit code";
+
+/// A decoded request whose pseudo-header fields have been validated by `pseudo::validate_request`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct RequestHeaders {
+    headers: Vec<Header>,
+    pseudo_count: usize,
+}
+
+/// A decoded response whose pseudo-header fields have been validated by `pseudo::validate_response`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ResponseHeaders {
+    headers: Vec<Header>,
+    status: u16,
+}
+
+impl RequestHeaders {
+    /// Builds a typed view of a decoded request
+    ///
+    /// ## Arguments
+    ///
+    /// * headers - the decoded headers, in the order they were received
+    ///
+    /// ## Returns
+    ///
+    /// * Result<RequestHeaders,&'static str> - the request, or an error if its pseudo-header fields are invalid
+    pub fn new(headers: Vec<Header>) -> Result<RequestHeaders, &'static str> {
+        pseudo::validate_request(&as_pairs(&headers))?;
+        let pseudo_count = count_pseudo(&headers);
+
+        Ok(RequestHeaders{headers, pseudo_count})
+    }
+
+    /// Function that returns the `:method` pseudo-header, always present
+    pub fn method(&self) -> &str {
+        self.pseudo(":method").expect("validated request has a :method")
+    }
+
+    /// Function that returns the `:scheme` pseudo-header, absent on plain CONNECT requests
+    pub fn scheme(&self) -> Option<&str> {
+        self.pseudo(":scheme")
+    }
+
+    /// Function that returns the `:path` pseudo-header, absent on plain CONNECT requests
+    pub fn path(&self) -> Option<&str> {
+        self.pseudo(":path")
+    }
+
+    /// Function that returns the `:authority` pseudo-header, if any
+    pub fn authority(&self) -> Option<&str> {
+        self.pseudo(":authority")
+    }
+
+    /// Function that returns the `:protocol` pseudo-header of an extended CONNECT request, if any
+    pub fn protocol(&self) -> Option<&str> {
+        self.pseudo(":protocol")
+    }
+
+    /// Function that returns the regular header fields, following the pseudo-header fields
+    pub fn fields(&self) -> &[Header] {
+        &self.headers[self.pseudo_count..]
+    }
+
+    /// Function that returns the value of the first regular header field with the given name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        find(self.fields(), name)
+    }
+
+    /// Function that consumes the view, returning every decoded header
+    pub fn into_inner(self) -> Vec<Header> {
+        self.headers
+    }
+
+    fn pseudo(&self, name: &str) -> Option<&str> {
+        find(&self.headers[..self.pseudo_count], name)
+    }
+}
+
+impl ResponseHeaders {
+    /// Builds a typed view of a decoded response
+    ///
+    /// ## Arguments
+    ///
+    /// * headers - the decoded headers, in the order they were received
+    ///
+    /// ## Returns
+    ///
+    /// * Result<ResponseHeaders,&'static str> - the response, or an error if its pseudo-header fields are invalid
+    pub fn new(headers: Vec<Header>) -> Result<ResponseHeaders, &'static str> {
+        pseudo::validate_response(&as_pairs(&headers))?;
+
+        let status = headers[0].value();
+        if status.len() != 3 || !status.bytes().all(|x| x.is_ascii_digit()) {
+            return Err(ERROR_INVALID_STATUS);
+        }
+        let status = status.parse().map_err(|_| ERROR_INVALID_STATUS)?;
+
+        Ok(ResponseHeaders{headers, status})
+    }
+
+    /// Function that returns the `:status` pseudo-header as a number
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Function that returns the regular header fields, following the `:status` pseudo-header
+    pub fn fields(&self) -> &[Header] {
+        &self.headers[1..]
+    }
+
+    /// Function that returns the value of the first regular header field with the given name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        find(self.fields(), name)
+    }
+
+    /// Function that consumes the view, returning every decoded header
+    pub fn into_inner(self) -> Vec<Header> {
+        self.headers
+    }
+}
+
+impl TryFrom<Vec<Header>> for RequestHeaders {
+    type Error = &'static str;
+
+    fn try_from(headers: Vec<Header>) -> Result<RequestHeaders, &'static str> {
+        RequestHeaders::new(headers)
+    }
+}
+
+impl TryFrom<HeaderList> for RequestHeaders {
+    type Error = &'static str;
+
+    fn try_from(headers: HeaderList) -> Result<RequestHeaders, &'static str> {
+        RequestHeaders::new(headers.into_inner())
+    }
+}
+
+impl TryFrom<Vec<Header>> for ResponseHeaders {
+    type Error = &'static str;
+
+    fn try_from(headers: Vec<Header>) -> Result<ResponseHeaders, &'static str> {
+        ResponseHeaders::new(headers)
+    }
+}
+
+impl TryFrom<HeaderList> for ResponseHeaders {
+    type Error = &'static str;
+
+    fn try_from(headers: HeaderList) -> Result<ResponseHeaders, &'static str> {
+        ResponseHeaders::new(headers.into_inner())
+    }
+}
+
+fn count_pseudo(headers: &[Header]) -> usize {
+    headers.iter().take_while(|h| h.name().starts_with(':')).count()
+}
+
+fn find<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers.iter().find(|h| h.name() == name).map(Header::value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hpack::Hpack;
+    use crate::testing::{REQUESTS_WITH_HUFFMAN, RESPONSES_WITH_HUFFMAN};
+
+    #[test]
+    fn test_request_headers(){
+        let mut hpack = Hpack::new(REQUESTS_WITH_HUFFMAN.dynamic_table_size);
+        for block in &REQUESTS_WITH_HUFFMAN.blocks[..2] {
+            hpack.read_headers(block.input.to_vec()).unwrap();
+        }
+        let request = RequestHeaders::new(hpack.read_headers(REQUESTS_WITH_HUFFMAN.blocks[2].input.to_vec()).unwrap()).unwrap();
+
+        assert_eq!("GET", request.method());
+        assert_eq!(Some("https"), request.scheme());
+        assert_eq!(Some("/index.html"), request.path());
+        assert_eq!(Some("www.example.com"), request.authority());
+        assert_eq!(None, request.protocol());
+        assert_eq!(1, request.fields().len());
+        assert_eq!(Some("custom-value"), request.get("custom-key"));
+        assert_eq!(None, request.get(":method"));
+    }
+
+    #[test]
+    fn test_request_headers_invalid(){
+        let mut hpack = Hpack::new(4096);
+        let headers = hpack.read_headers(vec![130_u8, 132_u8]).unwrap();
+
+        assert!(RequestHeaders::new(headers).is_err());
+    }
+
+    #[test]
+    fn test_response_headers(){
+        let mut hpack = Hpack::new(RESPONSES_WITH_HUFFMAN.dynamic_table_size);
+        let response = ResponseHeaders::try_from(hpack.read_headers(RESPONSES_WITH_HUFFMAN.blocks[0].input.to_vec()).unwrap()).unwrap();
+
+        assert_eq!(302, response.status());
+        assert_eq!(Some("private"), response.get("cache-control"));
+        assert_eq!(Some("https://www.example.com"), response.get("location"));
+        assert_eq!(3, response.fields().len());
+    }
+
+    #[test]
+    fn test_response_headers_invalid_status(){
+        let mut hpack = Hpack::new(4096);
+        let headers = hpack.read_headers(vec![72_u8, 2_u8, 0x32, 0x30]).unwrap();
+
+        assert_eq!(ERROR_INVALID_STATUS, ResponseHeaders::new(headers).unwrap_err());
+    }
+}
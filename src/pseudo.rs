@@ -0,0 +1,144 @@
+static ERROR_PSEUDO_AFTER_REGULAR: &str = "Error - Pseudo-header field after regular header field";
+static ERROR_UNKNOWN_PSEUDO: &str = "Error - Unknown pseudo-header field";
+static ERROR_DUPLICATE_PSEUDO: &str = "Error - Duplicate pseudo-header field";
+static ERROR_MISSING_PSEUDO: &str = "Error - Missing mandatory pseudo-header field";
+static ERROR_CONNECT_PSEUDO: &str = "Error - CONNECT request must only carry :method and :authority";
+static ERROR_PROTOCOL_NOT_CONNECT: &str = "Error - :protocol is only allowed on CONNECT requests";
+
+/// Pseudo-header fields allowed in requests, including `:protocol` from [IETF RFC 8441 Section 4](https://tools.ietf.org/html/rfc8441#section-4)
+pub static REQUEST_PSEUDO_HEADERS: [&str; 5] = [":method", ":scheme", ":authority", ":path", ":protocol"];
+
+/// Pseudo-header fields allowed in responses
+pub static RESPONSE_PSEUDO_HEADERS: [&str; 1] = [":status"];
+
+/// Function that validates the pseudo-header fields of a request as per [IETF RFC 7540 Section 8.1.2](https://tools.ietf.org/html/rfc7540#section-8.1.2).
+/// An extended CONNECT request, one carrying `:protocol`, must also carry `:scheme` and `:path` as per
+/// [IETF RFC 8441 Section 4](https://tools.ietf.org/html/rfc8441#section-4), while `:authority` stays optional as for any other request
+///
+/// ## Arguments
+///
+/// * headers - the name and value pairs of the request, in order
+///
+/// ## Returns
+///
+/// * Result<(),&'static str> - nothing, or an error message describing the first violation
+pub fn validate_request(headers: &[(&str, &str)]) -> Result<(), &'static str> {
+    let pseudo = collect_pseudo(headers, &REQUEST_PSEUDO_HEADERS)?;
+    let find = |name: &str| pseudo.iter().find(|x| x.0 == name).map(|x| x.1);
+
+    match (find(":method"), find(":protocol")) {
+        (None, _) => Err(ERROR_MISSING_PSEUDO),
+        (Some(method), Some(_)) if method != "CONNECT" => Err(ERROR_PROTOCOL_NOT_CONNECT),
+        (Some(_), Some(_)) => {
+            if find(":scheme").is_none() || find(":path").is_none() {
+                Err(ERROR_MISSING_PSEUDO)
+            } else {
+                Ok(())
+            }
+        },
+        (Some("CONNECT"), None) => {
+            if find(":scheme").is_some() || find(":path").is_some() {
+                Err(ERROR_CONNECT_PSEUDO)
+            } else if find(":authority").is_none() {
+                Err(ERROR_MISSING_PSEUDO)
+            } else {
+                Ok(())
+            }
+        },
+        (Some(_), None) => {
+            if find(":scheme").is_none() || find(":path").is_none() {
+                Err(ERROR_MISSING_PSEUDO)
+            } else {
+                Ok(())
+            }
+        },
+    }
+}
+
+/// Function that validates the pseudo-header fields of a response as per [IETF RFC 7540 Section 8.1.2.4](https://tools.ietf.org/html/rfc7540#section-8.1.2.4)
+///
+/// ## Arguments
+///
+/// * headers - the name and value pairs of the response, in order
+///
+/// ## Returns
+///
+/// * Result<(),&'static str> - nothing, or an error message describing the first violation
+pub fn validate_response(headers: &[(&str, &str)]) -> Result<(), &'static str> {
+    let pseudo = collect_pseudo(headers, &RESPONSE_PSEUDO_HEADERS)?;
+
+    if pseudo.is_empty() {
+        Err(ERROR_MISSING_PSEUDO)
+    } else {
+        Ok(())
+    }
+}
+
+/// Function that checks every pseudo-header is allowed, appears once, and precedes all regular header fields
+///
+/// ## Returns
+///
+/// * Result<Vec<(&str, &str)>,&'static str> - the pseudo-header fields in order, or an error message
+fn collect_pseudo<'a>(headers: &[(&'a str, &'a str)], allowed: &[&str]) -> Result<Vec<(&'a str, &'a str)>, &'static str> {
+    let mut pseudo: Vec<(&str, &str)> = Vec::new();
+    let mut seen_regular = false;
+
+    for (name, value) in headers {
+        if !name.starts_with(':') {
+            seen_regular = true;
+        } else if seen_regular {
+            return Err(ERROR_PSEUDO_AFTER_REGULAR);
+        } else if !allowed.contains(name) {
+            return Err(ERROR_UNKNOWN_PSEUDO);
+        } else if pseudo.iter().any(|x| x.0 == *name) {
+            return Err(ERROR_DUPLICATE_PSEUDO);
+        } else {
+            pseudo.push((name, value));
+        }
+    }
+    Ok(pseudo)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_request(){
+        assert!(validate_request(&[(":method", "GET"), (":scheme", "https"), (":path", "/"), ("accept", "*/*")]).is_ok());
+        assert_eq!(ERROR_MISSING_PSEUDO, validate_request(&[(":method", "GET"), (":path", "/")]).unwrap_err());
+        assert_eq!(ERROR_DUPLICATE_PSEUDO, validate_request(&[(":method", "GET"), (":method", "GET")]).unwrap_err());
+        assert_eq!(ERROR_UNKNOWN_PSEUDO, validate_request(&[(":status", "200")]).unwrap_err());
+    }
+
+    #[test]
+    fn test_validate_request_pseudo_after_regular(){
+        let headers = [(":method", "GET"), (":scheme", "https"), ("accept", "*/*"), (":path", "/")];
+
+        assert_eq!(ERROR_PSEUDO_AFTER_REGULAR, validate_request(&headers).unwrap_err());
+    }
+
+    #[test]
+    fn test_validate_request_connect(){
+        assert!(validate_request(&[(":method", "CONNECT"), (":authority", "example.com:443")]).is_ok());
+        assert_eq!(ERROR_CONNECT_PSEUDO, validate_request(&[(":method", "CONNECT"), (":authority", "example.com:443"), (":path", "/")]).unwrap_err());
+    }
+
+    #[test]
+    fn test_validate_request_extended_connect(){
+        let headers = [(":method", "CONNECT"), (":protocol", "websocket"), (":scheme", "https"), (":path", "/chat"), (":authority", "example.com"), ("sec-websocket-version", "13")];
+
+        assert!(validate_request(&headers).is_ok());
+        assert!(validate_request(&headers[..4]).is_ok());
+        assert_eq!(ERROR_MISSING_PSEUDO, validate_request(&[(":method", "CONNECT"), (":protocol", "websocket"), (":scheme", "https"), (":authority", "example.com")]).unwrap_err());
+        assert_eq!(ERROR_PROTOCOL_NOT_CONNECT, validate_request(&[(":method", "GET"), (":protocol", "websocket"), (":scheme", "https"), (":path", "/")]).unwrap_err());
+        assert_eq!(ERROR_PSEUDO_AFTER_REGULAR, validate_request(&[(":method", "CONNECT"), ("sec-websocket-version", "13"), (":protocol", "websocket")]).unwrap_err());
+    }
+
+    #[test]
+    fn test_validate_response(){
+        assert!(validate_response(&[(":status", "200"), ("server", "simple_hpack")]).is_ok());
+        assert_eq!(ERROR_MISSING_PSEUDO, validate_response(&[("server", "simple_hpack")]).unwrap_err());
+        assert_eq!(ERROR_UNKNOWN_PSEUDO, validate_response(&[(":status", "101"), (":protocol", "websocket")]).unwrap_err());
+    }
+}
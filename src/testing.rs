@@ -0,0 +1,420 @@
+use crate::dyn_table::{DynamicTable, Table};
+use crate::hpack::Hpack;
+
+/// One header block of an example sequence, along with the decoder state expected once it has been decoded
+pub struct Block {
+    pub input: &'static [u8],
+    pub headers: &'static [(&'static str, &'static str)],
+    /// Dynamic table entries after decoding the block, newest first
+    pub table: &'static [(&'static str, &'static str)],
+    /// Dynamic table size in bytes after decoding the block
+    pub table_size: usize,
+}
+
+/// A run of header blocks that must be decoded in order on the same connection
+pub struct Sequence {
+    pub name: &'static str,
+    pub dynamic_table_size: usize,
+    pub blocks: &'static [Block],
+}
+
+/// Function that returns every example sequence of [IETF RFC 7541 Appendix C](https://tools.ietf.org/html/rfc7541#appendix-C)
+pub fn sequences() -> [&'static Sequence; 4] {
+    [&REQUESTS_WITHOUT_HUFFMAN, &REQUESTS_WITH_HUFFMAN, &RESPONSES_WITHOUT_HUFFMAN, &RESPONSES_WITH_HUFFMAN]
+}
+
+/// Function that decodes a sequence with a fresh decoder, checking the headers and dynamic table after each block
+///
+/// ## Arguments
+///
+/// * sequence - the example sequence to check
+///
+/// ## Returns
+///
+/// * Result<(),String> - nothing, or a description of the first block that did not match
+pub fn check_sequence(sequence: &Sequence) -> Result<(), String> {
+    check_sequence_with(sequence, DynamicTable::new(sequence.dynamic_table_size))
+}
+
+/// Function that decodes a sequence with a fresh decoder built on the given table, checking the headers and table after each block
+///
+/// ## Arguments
+///
+/// * sequence - the example sequence to check
+/// * table - an empty table sized to `sequence.dynamic_table_size`
+///
+/// ## Returns
+///
+/// * Result<(),String> - nothing, or a description of the first block that did not match
+pub fn check_sequence_with<T: Table>(sequence: &Sequence, table: T) -> Result<(), String> {
+    let mut hpack = Hpack::with_table(table, sequence.dynamic_table_size);
+
+    for (i, block) in sequence.blocks.iter().enumerate() {
+        let headers = hpack.read_headers(block.input.to_vec())
+            .map_err(|e| format!("{} block {}: {}", sequence.name, i + 1, e))?;
+
+        let decoded: Vec<(&str, &str)> = headers.iter().map(|h| (h.name(), h.value())).collect();
+        if decoded != block.headers {
+            return Err(format!("{} block {}: expected headers {:?}, decoded {:?}", sequence.name, i + 1, block.headers, decoded));
+        }
+
+        let table = hpack.table();
+        let entries: Vec<(&str, &str)> = (0..table.len()).filter_map(|x| table.get(x)).collect();
+        if entries != block.table || table.size() != block.table_size {
+            return Err(format!("{} block {}: expected table {:?} of size {}, found {:?} of size {}",
+                sequence.name, i + 1, block.table, block.table_size, entries, table.size()));
+        }
+    }
+    Ok(())
+}
+
+/// Request Examples without Huffman Coding as per [IETF RFC 7541 Appendix C.3](https://tools.ietf.org/html/rfc7541#appendix-C.3)
+pub static REQUESTS_WITHOUT_HUFFMAN: Sequence = Sequence{
+    name: "C.3",
+    dynamic_table_size: 4096,
+    blocks: &[
+        Block{
+            input: &[
+                0x82, 0x86, 0x84, 0x41, 0x0f, 0x77, 0x77, 0x77, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65,
+                0x2e, 0x63, 0x6f, 0x6d,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+            ],
+            table: &[
+                (":authority", "www.example.com"),
+            ],
+            table_size: 57,
+        },
+        Block{
+            input: &[
+                0x82, 0x86, 0x84, 0xbe, 0x58, 0x08, 0x6e, 0x6f, 0x2d, 0x63, 0x61, 0x63, 0x68, 0x65,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+                ("cache-control", "no-cache"),
+            ],
+            table: &[
+                ("cache-control", "no-cache"),
+                (":authority", "www.example.com"),
+            ],
+            table_size: 110,
+        },
+        Block{
+            input: &[
+                0x82, 0x87, 0x85, 0xbf, 0x40, 0x0a, 0x63, 0x75, 0x73, 0x74, 0x6f, 0x6d, 0x2d, 0x6b, 0x65, 0x79,
+                0x0c, 0x63, 0x75, 0x73, 0x74, 0x6f, 0x6d, 0x2d, 0x76, 0x61, 0x6c, 0x75, 0x65,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "https"),
+                (":path", "/index.html"),
+                (":authority", "www.example.com"),
+                ("custom-key", "custom-value"),
+            ],
+            table: &[
+                ("custom-key", "custom-value"),
+                ("cache-control", "no-cache"),
+                (":authority", "www.example.com"),
+            ],
+            table_size: 164,
+        },
+    ],
+};
+
+/// Request Examples with Huffman Coding as per [IETF RFC 7541 Appendix C.4](https://tools.ietf.org/html/rfc7541#appendix-C.4)
+pub static REQUESTS_WITH_HUFFMAN: Sequence = Sequence{
+    name: "C.4",
+    dynamic_table_size: 4096,
+    blocks: &[
+        Block{
+            input: &[
+                0x82, 0x86, 0x84, 0x41, 0x8c, 0xf1, 0xe3, 0xc2, 0xe5, 0xf2, 0x3a, 0x6b, 0xa0, 0xab, 0x90, 0xf4,
+                0xff,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+            ],
+            table: &[
+                (":authority", "www.example.com"),
+            ],
+            table_size: 57,
+        },
+        Block{
+            input: &[
+                0x82, 0x86, 0x84, 0xbe, 0x58, 0x86, 0xa8, 0xeb, 0x10, 0x64, 0x9c, 0xbf,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "http"),
+                (":path", "/"),
+                (":authority", "www.example.com"),
+                ("cache-control", "no-cache"),
+            ],
+            table: &[
+                ("cache-control", "no-cache"),
+                (":authority", "www.example.com"),
+            ],
+            table_size: 110,
+        },
+        Block{
+            input: &[
+                0x82, 0x87, 0x85, 0xbf, 0x40, 0x88, 0x25, 0xa8, 0x49, 0xe9, 0x5b, 0xa9, 0x7d, 0x7f, 0x89, 0x25,
+                0xa8, 0x49, 0xe9, 0x5b, 0xb8, 0xe8, 0xb4, 0xbf,
+            ],
+            headers: &[
+                (":method", "GET"),
+                (":scheme", "https"),
+                (":path", "/index.html"),
+                (":authority", "www.example.com"),
+                ("custom-key", "custom-value"),
+            ],
+            table: &[
+                ("custom-key", "custom-value"),
+                ("cache-control", "no-cache"),
+                (":authority", "www.example.com"),
+            ],
+            table_size: 164,
+        },
+    ],
+};
+
+/// Response Examples without Huffman Coding as per [IETF RFC 7541 Appendix C.5](https://tools.ietf.org/html/rfc7541#appendix-C.5)
+pub static RESPONSES_WITHOUT_HUFFMAN: Sequence = Sequence{
+    name: "C.5",
+    dynamic_table_size: 256,
+    blocks: &[
+        Block{
+            input: &[
+                0x48, 0x03, 0x33, 0x30, 0x32, 0x58, 0x07, 0x70, 0x72, 0x69, 0x76, 0x61, 0x74, 0x65, 0x61, 0x1d,
+                0x4d, 0x6f, 0x6e, 0x2c, 0x20, 0x32, 0x31, 0x20, 0x4f, 0x63, 0x74, 0x20, 0x32, 0x30, 0x31, 0x33,
+                0x20, 0x32, 0x30, 0x3a, 0x31, 0x33, 0x3a, 0x32, 0x31, 0x20, 0x47, 0x4d, 0x54, 0x6e, 0x17, 0x68,
+                0x74, 0x74, 0x70, 0x73, 0x3a, 0x2f, 0x2f, 0x77, 0x77, 0x77, 0x2e, 0x65, 0x78, 0x61, 0x6d, 0x70,
+                0x6c, 0x65, 0x2e, 0x63, 0x6f, 0x6d,
+            ],
+            headers: &[
+                (":status", "302"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("location", "https://www.example.com"),
+            ],
+            table: &[
+                ("location", "https://www.example.com"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("cache-control", "private"),
+                (":status", "302"),
+            ],
+            table_size: 222,
+        },
+        Block{
+            input: &[
+                0x48, 0x03, 0x33, 0x30, 0x37, 0xc1, 0xc0, 0xbf,
+            ],
+            headers: &[
+                (":status", "307"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("location", "https://www.example.com"),
+            ],
+            table: &[
+                (":status", "307"),
+                ("location", "https://www.example.com"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("cache-control", "private"),
+            ],
+            table_size: 222,
+        },
+        Block{
+            input: &[
+                0x88, 0xc1, 0x61, 0x1d, 0x4d, 0x6f, 0x6e, 0x2c, 0x20, 0x32, 0x31, 0x20, 0x4f, 0x63, 0x74, 0x20,
+                0x32, 0x30, 0x31, 0x33, 0x20, 0x32, 0x30, 0x3a, 0x31, 0x33, 0x3a, 0x32, 0x32, 0x20, 0x47, 0x4d,
+                0x54, 0xc0, 0x5a, 0x04, 0x67, 0x7a, 0x69, 0x70, 0x77, 0x38, 0x66, 0x6f, 0x6f, 0x3d, 0x41, 0x53,
+                0x44, 0x4a, 0x4b, 0x48, 0x51, 0x4b, 0x42, 0x5a, 0x58, 0x4f, 0x51, 0x57, 0x45, 0x4f, 0x50, 0x49,
+                0x55, 0x41, 0x58, 0x51, 0x57, 0x45, 0x4f, 0x49, 0x55, 0x3b, 0x20, 0x6d, 0x61, 0x78, 0x2d, 0x61,
+                0x67, 0x65, 0x3d, 0x33, 0x36, 0x30, 0x30, 0x3b, 0x20, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+                0x3d, 0x31,
+            ],
+            headers: &[
+                (":status", "200"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+                ("location", "https://www.example.com"),
+                ("content-encoding", "gzip"),
+                ("set-cookie", "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"),
+            ],
+            table: &[
+                ("set-cookie", "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"),
+                ("content-encoding", "gzip"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+            ],
+            table_size: 215,
+        },
+    ],
+};
+
+/// Response Examples with Huffman Coding as per [IETF RFC 7541 Appendix C.6](https://tools.ietf.org/html/rfc7541#appendix-C.6)
+pub static RESPONSES_WITH_HUFFMAN: Sequence = Sequence{
+    name: "C.6",
+    dynamic_table_size: 256,
+    blocks: &[
+        Block{
+            input: &[
+                0x48, 0x82, 0x64, 0x02, 0x58, 0x85, 0xae, 0xc3, 0x77, 0x1a, 0x4b, 0x61, 0x96, 0xd0, 0x7a, 0xbe,
+                0x94, 0x10, 0x54, 0xd4, 0x44, 0xa8, 0x20, 0x05, 0x95, 0x04, 0x0b, 0x81, 0x66, 0xe0, 0x82, 0xa6,
+                0x2d, 0x1b, 0xff, 0x6e, 0x91, 0x9d, 0x29, 0xad, 0x17, 0x18, 0x63, 0xc7, 0x8f, 0x0b, 0x97, 0xc8,
+                0xe9, 0xae, 0x82, 0xae, 0x43, 0xd3,
+            ],
+            headers: &[
+                (":status", "302"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("location", "https://www.example.com"),
+            ],
+            table: &[
+                ("location", "https://www.example.com"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("cache-control", "private"),
+                (":status", "302"),
+            ],
+            table_size: 222,
+        },
+        Block{
+            input: &[
+                0x48, 0x83, 0x64, 0x0e, 0xff, 0xc1, 0xc0, 0xbf,
+            ],
+            headers: &[
+                (":status", "307"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("location", "https://www.example.com"),
+            ],
+            table: &[
+                (":status", "307"),
+                ("location", "https://www.example.com"),
+                ("date", "Mon, 21 Oct 2013 20:13:21 GMT"),
+                ("cache-control", "private"),
+            ],
+            table_size: 222,
+        },
+        Block{
+            input: &[
+                0x88, 0xc1, 0x61, 0x96, 0xd0, 0x7a, 0xbe, 0x94, 0x10, 0x54, 0xd4, 0x44, 0xa8, 0x20, 0x05, 0x95,
+                0x04, 0x0b, 0x81, 0x66, 0xe0, 0x84, 0xa6, 0x2d, 0x1b, 0xff, 0xc0, 0x5a, 0x83, 0x9b, 0xd9, 0xab,
+                0x77, 0xad, 0x94, 0xe7, 0x82, 0x1d, 0xd7, 0xf2, 0xe6, 0xc7, 0xb3, 0x35, 0xdf, 0xdf, 0xcd, 0x5b,
+                0x39, 0x60, 0xd5, 0xaf, 0x27, 0x08, 0x7f, 0x36, 0x72, 0xc1, 0xab, 0x27, 0x0f, 0xb5, 0x29, 0x1f,
+                0x95, 0x87, 0x31, 0x60, 0x65, 0xc0, 0x03, 0xed, 0x4e, 0xe5, 0xb1, 0x06, 0x3d, 0x50, 0x07,
+            ],
+            headers: &[
+                (":status", "200"),
+                ("cache-control", "private"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+                ("location", "https://www.example.com"),
+                ("content-encoding", "gzip"),
+                ("set-cookie", "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"),
+            ],
+            table: &[
+                ("set-cookie", "foo=ASDJKHQKBZXOQWEOPIUAXQWEOIU; max-age=3600; version=1"),
+                ("content-encoding", "gzip"),
+                ("date", "Mon, 21 Oct 2013 20:13:22 GMT"),
+            ],
+            table_size: 215,
+        },
+    ],
+};
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoder::Encoder;
+    use std::collections::VecDeque;
+
+    struct DequeTable{
+        entries: VecDeque<(String, String)>,
+        max_size: usize,
+        size: usize,
+    }
+
+    impl DequeTable{
+        fn evict_to(&mut self, size: usize) {
+            while self.size > size {
+                let (name, value) = self.entries.pop_back().unwrap();
+                self.size -= name.len() + value.len() + 32;
+            }
+        }
+    }
+
+    impl Table for DequeTable{
+        fn get(&self, index: usize) -> Option<(&str, &str)> {
+            self.entries.get(index).map(|x| (x.0.as_str(), x.1.as_str()))
+        }
+
+        fn add(&mut self, header: (String, String)) -> Result<(), &'static str> {
+            let header_size = header.0.len() + header.1.len() + 32;
+            if header_size > self.max_size {
+                self.evict_to(0);
+                return Err("Header exceeds table size!");
+            }
+            self.evict_to(self.max_size - header_size);
+            self.size += header_size;
+            self.entries.push_front(header);
+            Ok(())
+        }
+
+        fn set_size(&mut self, new_size: usize) {
+            self.max_size = new_size;
+            self.evict_to(new_size);
+        }
+
+        fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        fn size(&self) -> usize {
+            self.size
+        }
+    }
+
+    #[test]
+    fn test_requests_without_huffman(){
+        check_sequence(&REQUESTS_WITHOUT_HUFFMAN).unwrap();
+    }
+
+    #[test]
+    fn test_requests_with_huffman(){
+        check_sequence(&REQUESTS_WITH_HUFFMAN).unwrap();
+    }
+
+    #[test]
+    fn test_responses_without_huffman(){
+        check_sequence(&RESPONSES_WITHOUT_HUFFMAN).unwrap();
+    }
+
+    #[test]
+    fn test_responses_with_huffman(){
+        check_sequence(&RESPONSES_WITH_HUFFMAN).unwrap();
+    }
+
+    #[test]
+    fn test_sequences_with_custom_table(){
+        for sequence in sequences() {
+            check_sequence_with(sequence, DequeTable{entries: VecDeque::new(), max_size: sequence.dynamic_table_size, size: 0}).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_encoder_requests_without_huffman(){
+        let mut encoder = Encoder::new(REQUESTS_WITHOUT_HUFFMAN.dynamic_table_size);
+
+        for block in REQUESTS_WITHOUT_HUFFMAN.blocks {
+            assert_eq!(block.input, encoder.encode(block.headers).unwrap().as_slice());
+        }
+    }
+}
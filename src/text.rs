@@ -0,0 +1,295 @@
+use std::io::{self, Read, Write};
+
+static BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+static HEX: &[u8; 16] = b"0123456789abcdef";
+
+static ERROR_INVALID_BASE64: &str = "Error - Invalid base64url character";
+static ERROR_TRUNCATED_BASE64: &str = "Error - Truncated base64url input";
+static ERROR_INVALID_HEX: &str = "Error - Invalid hex character";
+static ERROR_TRUNCATED_HEX: &str = "Error - Odd number of hex digits";
+
+/// Function that serializes an encoded header block to unpadded base64url, the alphabet used to carry
+/// HTTP2-Settings as per [IETF RFC 7540 Section 3.2.1](https://tools.ietf.org/html/rfc7540#section-3.2.1)
+///
+/// ## Arguments
+///
+/// * block - the bytes of the header block
+///
+/// ## Returns
+///
+/// * String - the base64url text without padding
+pub fn to_base64url(block: &[u8]) -> String {
+    let mut text = Vec::with_capacity((block.len() * 4).div_ceil(3));
+    for chunk in block.chunks(3) {
+        encode_group(chunk, &mut text);
+    }
+    String::from_utf8(text).expect("base64url alphabet is ascii")
+}
+
+/// Function that reads a header block back from base64url text, padding and whitespace are ignored
+///
+/// ## Arguments
+///
+/// * text - the base64url text
+///
+/// ## Returns
+///
+/// * Result<Vec<u8>,&'static str> - the bytes of the header block or an error message
+pub fn from_base64url(text: &str) -> Result<Vec<u8>, &'static str> {
+    let chars: Vec<u8> = text.bytes().filter(|c| !skip_char(*c)).collect();
+    let mut block = Vec::with_capacity(chars.len() * 3 / 4);
+    for group in chars.chunks(4) {
+        decode_group(group, &mut block)?;
+    }
+    Ok(block)
+}
+
+/// Function that serializes an encoded header block to lowercase hex
+///
+/// ## Arguments
+///
+/// * block - the bytes of the header block
+///
+/// ## Returns
+///
+/// * String - two hex digits per byte
+pub fn to_hex(block: &[u8]) -> String {
+    let mut text = String::with_capacity(block.len() * 2);
+    for byte in block {
+        text.push(HEX[(byte >> 4) as usize] as char);
+        text.push(HEX[(byte & 15) as usize] as char);
+    }
+    text
+}
+
+/// Function that reads a header block back from hex text, either case is accepted and whitespace is ignored
+///
+/// ## Arguments
+///
+/// * text - the hex text
+///
+/// ## Returns
+///
+/// * Result<Vec<u8>,&'static str> - the bytes of the header block or an error message
+pub fn from_hex(text: &str) -> Result<Vec<u8>, &'static str> {
+    let digits = text.bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| (c as char).to_digit(16).ok_or(ERROR_INVALID_HEX))
+        .collect::<Result<Vec<u32>, &'static str>>()?;
+
+    if digits.len() % 2 != 0 {
+        return Err(ERROR_TRUNCATED_HEX);
+    }
+    Ok(digits.chunks(2).map(|pair| (pair[0] * 16 + pair[1]) as u8).collect())
+}
+
+/// Writer that base64url encodes everything written to it into an inner writer, so large blocks can be
+/// dumped without holding the text in memory. `finish` must be called to flush the final partial group.
+pub struct Base64UrlWriter<W: Write> {
+    inner: W,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> Base64UrlWriter<W> {
+    pub fn new(inner: W) -> Base64UrlWriter<W> {
+        Base64UrlWriter{inner, pending: Vec::with_capacity(3)}
+    }
+
+    /// Function that writes out any remaining bytes and returns the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut text = Vec::with_capacity(4);
+        encode_group(&self.pending, &mut text);
+        self.inner.write_all(&text)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Base64UrlWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut bytes = Vec::with_capacity(self.pending.len() + buf.len());
+        bytes.extend_from_slice(&self.pending);
+        bytes.extend_from_slice(buf);
+        let whole = bytes.len() - bytes.len() % 3;
+
+        let mut text = Vec::with_capacity(whole / 3 * 4);
+        for chunk in bytes[..whole].chunks(3) {
+            encode_group(chunk, &mut text);
+        }
+        // Only keep the new bytes once the inner writer took them, an error means nothing was written
+        self.inner.write_all(&text)?;
+        bytes.drain(..whole);
+        self.pending = bytes;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader that decodes base64url text from an inner reader, yielding the bytes of the header block.
+/// Padding and whitespace in the text are ignored.
+pub struct Base64UrlReader<R: Read> {
+    inner: R,
+    chars: Vec<u8>,
+    decoded: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> Base64UrlReader<R> {
+    pub fn new(inner: R) -> Base64UrlReader<R> {
+        Base64UrlReader{inner, chars: Vec::new(), decoded: Vec::new(), done: false}
+    }
+
+    /// Function that returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Base64UrlReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let invalid = |e: &'static str| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut chunk = [0_u8; 1024];
+
+        while self.decoded.is_empty() && !self.done {
+            let read = self.inner.read(&mut chunk)?;
+            if read == 0 {
+                self.done = true;
+                decode_group(&self.chars, &mut self.decoded).map_err(invalid)?;
+                self.chars.clear();
+            } else {
+                self.chars.extend(chunk[..read].iter().filter(|c| !skip_char(**c)));
+                let whole = self.chars.len() - self.chars.len() % 4;
+                for group in self.chars[..whole].chunks(4) {
+                    decode_group(group, &mut self.decoded).map_err(invalid)?;
+                }
+                self.chars.drain(..whole);
+            }
+        }
+
+        let len = buf.len().min(self.decoded.len());
+        buf[..len].copy_from_slice(&self.decoded[..len]);
+        self.decoded.drain(..len);
+        Ok(len)
+    }
+}
+
+/// Function that encodes up to three bytes as up to four base64url characters, without padding
+fn encode_group(chunk: &[u8], text: &mut Vec<u8>) {
+    if chunk.is_empty() {
+        return;
+    }
+    let bits = chunk.iter().enumerate().fold(0_u32, |acc, (i, b)| acc | (*b as u32) << (16 - 8 * i));
+    for i in 0..=chunk.len() {
+        text.push(BASE64URL[((bits >> (18 - 6 * i)) & 63) as usize]);
+    }
+}
+
+/// Function that decodes up to four base64url characters into up to three bytes
+fn decode_group(group: &[u8], block: &mut Vec<u8>) -> Result<(), &'static str> {
+    if group.is_empty() {
+        return Ok(());
+    }
+    if group.len() == 1 {
+        return Err(ERROR_TRUNCATED_BASE64);
+    }
+
+    let mut bits = 0_u32;
+    for (i, c) in group.iter().enumerate() {
+        let value = match BASE64URL.iter().position(|x| x == c) {
+            Some(x) => x as u32,
+            None => return Err(ERROR_INVALID_BASE64),
+        };
+        bits |= value << (18 - 6 * i);
+    }
+    for i in 0..group.len() - 1 {
+        block.push((bits >> (16 - 8 * i)) as u8);
+    }
+    Ok(())
+}
+
+fn skip_char(c: u8) -> bool {
+    c == b'=' || c.is_ascii_whitespace()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_base64url_round_trip(){
+        let block = vec![130_u8, 134_u8, 132_u8, 65_u8, 15_u8, 0xfb, 0xff];
+
+        assert_eq!("goaEQQ_7_w", to_base64url(&block));
+        assert_eq!(block, from_base64url("goaEQQ_7_w").unwrap());
+    }
+
+    #[test]
+    fn test_base64url_ignores_padding_and_whitespace(){
+        assert_eq!(vec![130_u8, 134_u8, 132_u8, 65_u8], from_base64url("goaE\nQQ==").unwrap());
+    }
+
+    #[test]
+    fn test_base64url_invalid(){
+        assert_eq!(ERROR_INVALID_BASE64, from_base64url("go+E").unwrap_err());
+        assert_eq!(ERROR_TRUNCATED_BASE64, from_base64url("goaEQ").unwrap_err());
+    }
+
+    #[test]
+    fn test_hex_round_trip(){
+        let block = vec![130_u8, 134_u8, 132_u8, 65_u8, 15_u8];
+
+        assert_eq!("828684410f", to_hex(&block));
+        assert_eq!(block, from_hex("82 86 84 41 0F").unwrap());
+        assert_eq!(ERROR_TRUNCATED_HEX, from_hex("828").unwrap_err());
+        assert_eq!(ERROR_INVALID_HEX, from_hex("8g").unwrap_err());
+    }
+
+    #[test]
+    fn test_base64url_streaming_round_trip(){
+        let block: Vec<u8> = (0..5000).map(|i| (i * 7) as u8).collect();
+
+        let mut writer = Base64UrlWriter::new(Vec::new());
+        for chunk in block.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        let text = writer.finish().unwrap();
+        assert_eq!(to_base64url(&block).into_bytes(), text);
+
+        let mut decoded = Vec::new();
+        Base64UrlReader::new(text.as_slice()).read_to_end(&mut decoded).unwrap();
+        assert_eq!(block, decoded);
+    }
+
+    struct FailOnceWriter{
+        out: Vec<u8>,
+        fail: bool,
+    }
+
+    impl Write for FailOnceWriter{
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if std::mem::take(&mut self.fail) {
+                return Err(io::Error::other("inner writer failed"));
+            }
+            self.out.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_base64url_writer_keeps_bytes_on_error(){
+        let mut writer = Base64UrlWriter::new(FailOnceWriter{out: Vec::new(), fail: true});
+
+        writer.write_all(b"ab").unwrap();
+        assert!(writer.write(b"c").is_err());
+        writer.write_all(b"c").unwrap();
+
+        assert_eq!(to_base64url(b"abc").into_bytes(), writer.finish().unwrap().out);
+    }
+}